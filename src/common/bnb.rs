@@ -0,0 +1,282 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::Mutex,
+    thread::{self, available_parallelism},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // follows the first successor all the way down before trying the next, using little memory
+    DepthFirst,
+    // always expands the state with the highest bound, and stops as soon as nothing left can win
+    BestFirst,
+}
+
+// the best state found, with how much of the tree it took to find it
+#[derive(Debug, Clone)]
+pub struct BnbResult<S, V> {
+    pub value: V,
+    pub state: S,
+    pub nodes: usize,
+    pub pruned: usize,
+}
+
+// the highest `objective` of any state reachable from `root`, where `bound` must never be below
+// the objective of a state or anything reachable from it; states whose bound can't beat the best
+// found so far are skipped
+pub fn branch_and_bound<S, V, I>(
+    root: S,
+    strategy: Strategy,
+    mut successors: impl FnMut(&S) -> I,
+    mut objective: impl FnMut(&S) -> V,
+    mut bound: impl FnMut(&S) -> V,
+) -> BnbResult<S, V>
+where
+    S: Clone,
+    V: Copy + Ord,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = BnbResult {
+        value: objective(&root),
+        state: root.clone(),
+        nodes: 0,
+        pruned: 0,
+    };
+    let root_bound = bound(&root);
+    // the frontier keeps each state's bound, so it can be checked again against a newer best
+    let mut frontier = Frontier::new(strategy);
+    frontier.push(root_bound, root);
+    while let Some((state_bound, state)) = frontier.pop() {
+        // the root is always expanded, its bound can only tie with its own objective
+        if state_bound <= result.value && result.nodes > 0 {
+            if strategy == Strategy::BestFirst {
+                // everything still queued has a bound no higher than this one
+                result.pruned += frontier.len() + 1;
+                break;
+            }
+            result.pruned += 1;
+            continue;
+        }
+        result.nodes += 1;
+        let value = objective(&state);
+        if value > result.value {
+            result.value = value;
+            result.state = state.clone();
+        }
+        let children = successors(&state)
+            .into_iter()
+            .filter_map(|child| {
+                let child_bound = bound(&child);
+                if child_bound > result.value {
+                    Some((child_bound, child))
+                } else {
+                    result.pruned += 1;
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        frontier.extend(children);
+    }
+    result
+}
+
+// runs a separate search from each root, spread over `workers` threads, results are in root order
+pub fn branch_and_bound_each<S, V, I>(
+    roots: Vec<S>,
+    workers: usize,
+    strategy: Strategy,
+    successors: impl Fn(&S) -> I + Sync,
+    objective: impl Fn(&S) -> V + Sync,
+    bound: impl Fn(&S) -> V + Sync,
+) -> Vec<BnbResult<S, V>>
+where
+    S: Clone + Send,
+    V: Copy + Ord + Send,
+    I: IntoIterator<Item = S>,
+{
+    let roots = Mutex::new(roots.into_iter().enumerate());
+    let mut results = thread::scope(|scope| {
+        let handles = (0..workers.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    // the lock is only held while taking the next root
+                    while let Some((i, root)) = { roots.lock().unwrap().next() } {
+                        let result =
+                            branch_and_bound(root, strategy, &successors, &objective, &bound);
+                        results.push((i, result));
+                    }
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+pub fn available_workers() -> usize {
+    available_parallelism().map_or(1, |n| n.get())
+}
+
+enum Frontier<S, V> {
+    Stack(Vec<(V, S)>),
+    // ties on the bound go to the state queued first
+    Heap(BinaryHeap<(V, Reverse<usize>)>, Vec<Option<S>>),
+}
+
+impl<S, V: Copy + Ord> Frontier<S, V> {
+    fn new(strategy: Strategy) -> Self {
+        match strategy {
+            Strategy::DepthFirst => Frontier::Stack(vec![]),
+            Strategy::BestFirst => Frontier::Heap(BinaryHeap::new(), vec![]),
+        }
+    }
+
+    fn push(&mut self, bound: V, state: S) {
+        match self {
+            Frontier::Stack(stack) => stack.push((bound, state)),
+            Frontier::Heap(heap, states) => {
+                heap.push((bound, Reverse(states.len())));
+                states.push(Some(state));
+            }
+        }
+    }
+
+    // children in the order they were generated, so the first one is expanded first
+    fn extend(&mut self, children: Vec<(V, S)>) {
+        match self {
+            Frontier::Stack(stack) => stack.extend(children.into_iter().rev()),
+            Frontier::Heap(..) => {
+                for (bound, state) in children {
+                    self.push(bound, state);
+                }
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(V, S)> {
+        match self {
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Heap(heap, states) => {
+                let (bound, Reverse(id)) = heap.pop()?;
+                Some((bound, states[id].take().unwrap()))
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Frontier::Stack(stack) => stack.len(),
+            Frontier::Heap(heap, _) => heap.len(),
+        }
+    }
+}
+
+// 0/1 knapsack over (weight, value) items, a state is the next item to decide on with the
+// weight and value taken so far
+#[cfg(test)]
+const ITEMS: [(u32, u32); 8] = [
+    (12, 4),
+    (2, 2),
+    (1, 1),
+    (4, 10),
+    (1, 2),
+    (3, 7),
+    (9, 9),
+    (5, 6),
+];
+
+#[cfg(test)]
+fn knapsack(
+    capacity: u32,
+    strategy: Strategy,
+    use_bound: bool,
+) -> BnbResult<(usize, u32, u32), u32> {
+    branch_and_bound(
+        (0, 0, 0),
+        strategy,
+        |&(next, weight, value)| {
+            let mut children = vec![];
+            if let Some((w, v)) = ITEMS.get(next) {
+                if weight + w <= capacity {
+                    children.push((next + 1, weight + w, value + v));
+                }
+                children.push((next + 1, weight, value));
+            }
+            children
+        },
+        |&(_, _, value)| value,
+        |&(next, _, value)| {
+            if use_bound {
+                value + ITEMS[next..].iter().map(|(_, v)| v).sum::<u32>()
+            } else {
+                u32::MAX
+            }
+        },
+    )
+}
+
+#[test]
+fn test_branch_and_bound() {
+    let exhaustive = knapsack(15, Strategy::DepthFirst, false);
+    assert_eq!(exhaustive.value, 27);
+    assert_eq!(exhaustive.pruned, 0);
+    for strategy in [Strategy::DepthFirst, Strategy::BestFirst] {
+        let result = knapsack(15, strategy, true);
+        assert_eq!(result.value, 27);
+        assert!(result.state.1 <= 15);
+        assert!(result.nodes < exhaustive.nodes);
+        assert!(result.pruned > 0);
+    }
+    // nothing fits, so the root is the answer
+    assert_eq!(knapsack(0, Strategy::BestFirst, true).value, 0);
+}
+
+#[test]
+fn test_branch_and_bound_each() {
+    let capacities = [0, 1, 5, 15, 47];
+    let results = branch_and_bound_each(
+        capacities.to_vec(),
+        3,
+        Strategy::DepthFirst,
+        |&capacity| [capacity].into_iter().filter(|c| *c > 0).map(|c| c - 1),
+        |&capacity| capacity * 2,
+        |&capacity| capacity * 2,
+    );
+    // each root is already its own best state, and results come back in root order
+    assert_eq!(
+        results.iter().map(|r| r.value).collect::<Vec<_>>(),
+        vec![0, 2, 10, 30, 94]
+    );
+    assert!(results.iter().all(|r| r.nodes == 1 && r.pruned <= 1));
+    let results = branch_and_bound_each(
+        vec![(5, 0, 0, 0), (15, 0, 0, 0)],
+        1,
+        Strategy::BestFirst,
+        |&(capacity, next, weight, value): &(u32, usize, u32, u32)| {
+            let mut children = vec![];
+            if let Some((w, v)) = ITEMS.get(next) {
+                if weight + w <= capacity {
+                    children.push((capacity, next + 1, weight + w, value + v));
+                }
+                children.push((capacity, next + 1, weight, value));
+            }
+            children
+        },
+        |state| state.3,
+        |&(_, next, _, value)| value + ITEMS[next..].iter().map(|(_, v)| v).sum::<u32>(),
+    );
+    assert_eq!(
+        results.iter().map(|r| r.value).collect::<Vec<_>>(),
+        vec![
+            knapsack(5, Strategy::DepthFirst, false).value,
+            knapsack(15, Strategy::DepthFirst, false).value
+        ]
+    );
+}
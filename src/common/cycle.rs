@@ -0,0 +1,112 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::{Num, NumCast};
+
+// the state after `start` steps is the first to come back, `length` steps later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<M> {
+    pub start: usize,
+    pub length: usize,
+    // the metric after 0 ..= start + length steps
+    metrics: Vec<M>,
+}
+
+impl<M: Copy> Cycle<M> {
+    // for metrics that only depend on the state, like the position of something
+    pub fn value_at(&self, n: usize) -> M {
+        if n < self.metrics.len() {
+            self.metrics[n]
+        } else {
+            self.metrics[self.start + (n - self.start) % self.length]
+        }
+    }
+}
+
+impl<M: Copy + Num + NumCast> Cycle<M> {
+    // for metrics that grow by the same amount every time round the cycle, like a height
+    pub fn extrapolate(&self, n: usize) -> M {
+        if n < self.metrics.len() {
+            return self.metrics[n];
+        }
+        let cycles = (n - self.start) / self.length;
+        let offset = (n - self.start) % self.length;
+        let growth = self.metrics[self.start + self.length] - self.metrics[self.start];
+        let cycles = M::from(cycles).expect("cycle count should fit in the metric type");
+        self.metrics[self.start + offset] + growth * cycles
+    }
+}
+
+// steps `state` until a key repeats, recording the metric of every state on the way,
+// so this never returns if the keys don't eventually repeat
+pub fn find_cycle<S, K, M>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> M,
+) -> Cycle<M>
+where
+    K: Hash + Eq,
+{
+    let mut seen = HashMap::new();
+    let mut metrics = vec![];
+    loop {
+        let steps = metrics.len();
+        metrics.push(metric(&state));
+        if let Some(start) = seen.insert(key(&state), steps) {
+            return Cycle {
+                start,
+                length: steps - start,
+                metrics,
+            };
+        }
+        step(&mut state);
+    }
+}
+
+#[test]
+fn test_find_cycle() {
+    // 0 1 2 3 4 5 2 3 4 5 2 ...
+    let cycle = find_cycle(0, |x| *x = if *x == 5 { 2 } else { *x + 1 }, |x| *x, |x| *x);
+    assert_eq!((cycle.start, cycle.length), (2, 4));
+    let expected = [0, 1, 2, 3, 4, 5, 2, 3, 4, 5, 2, 3];
+    for (n, value) in expected.iter().enumerate() {
+        assert_eq!(cycle.value_at(n), *value);
+    }
+    assert_eq!(cycle.value_at(1_000_000_000), 2 + (1_000_000_000 - 2) % 4);
+
+    // a cycle from the very first state
+    let cycle = find_cycle(0, |x| *x = (*x + 1) % 3, |x| *x, |x| *x * 10);
+    assert_eq!((cycle.start, cycle.length), (0, 3));
+    assert_eq!(cycle.value_at(7), 10);
+
+    // a fixed point
+    let cycle = find_cycle(9, |_| {}, |x| *x, |x| *x);
+    assert_eq!((cycle.start, cycle.length), (0, 1));
+    assert_eq!(cycle.value_at(12345), 9);
+}
+
+#[test]
+fn test_cycle_extrapolate() {
+    // the phase goes 0 1 2 3 1 2 3 ... and the total adds up the phases seen so far
+    let cycle = find_cycle(
+        (0u64, 0u64),
+        |(phase, total)| {
+            *phase = *phase % 3 + 1;
+            *total += *phase;
+        },
+        |(phase, _)| *phase,
+        |(_, total)| *total,
+    );
+    assert_eq!((cycle.start, cycle.length), (1, 3));
+    let mut state = (0u64, 0u64);
+    for n in 0..50 {
+        assert_eq!(cycle.extrapolate(n), state.1, "after {} steps", n);
+        state.0 = state.0 % 3 + 1;
+        state.1 += state.0;
+    }
+    // 6 per cycle of 3 steps after the first step
+    assert_eq!(
+        cycle.extrapolate(1_000_000_000_000),
+        1 + (1_000_000_000_000 - 1) / 3 * 6
+    );
+}
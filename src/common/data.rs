@@ -1,12 +1,27 @@
-use std::{error::Error, fs};
+use std::{fs, time::SystemTime};
 
-use crate::common::data::answers::check_answer;
-use crate::common::data::{examples::fetch_examples, req::aoc_request};
+use chrono::{Duration, Utc};
 
-use super::SimpleResult;
+use crate::common::data::answers::{check_answer, dry_run_answer};
+use crate::common::data::{
+    examples::fetch_examples,
+    input::{validate_download, validate_input, verify_checksum, write_checksum},
+    leaderboard::{fetch_leaderboard, parse_leaderboard},
+    req::aoc_puzzle_request,
+};
+
+use super::{
+    error::{AocError, AocResult},
+    puzzle::{check_year, PuzzleId},
+};
+
+pub use self::input::normalize_input;
+pub use self::leaderboard::Leaderboard;
 
 mod answers;
 mod examples;
+mod input;
+mod leaderboard;
 mod req;
 
 pub fn input_to_ints(input: &str) -> Vec<i64> {
@@ -19,36 +34,45 @@ pub fn input_to_ints(input: &str) -> Vec<i64> {
     numbers
 }
 
-pub fn get_input(year: u32, day: u32) -> SimpleResult<String> {
-    assert!((2015..3000).contains(&year));
-    assert!((1..=25).contains(&day));
-    let data_folder = format!("./data/{}/{}", year, day);
+pub fn get_input(puzzle: PuzzleId) -> AocResult<String> {
+    let data_folder = format!("./data/{}/{}", puzzle.year(), puzzle.day());
     let input_filename = format!("{}/input.txt", data_folder);
-    fs::read_to_string(&input_filename).or_else(|_| -> Result<String, Box<dyn Error>> {
-        println!(
-            "Couldn't find input file {}, fetching from adventofcode.com",
-            input_filename
-        );
-        let fetched_input = fetch_input(year, day)?;
-        fs::create_dir_all(&data_folder)?;
-        fs::write(input_filename, &fetched_input)?;
-        Ok(fetched_input)
-    })
+    let checksum_filename = format!("{}/input.checksum", data_folder);
+    match fs::read_to_string(&input_filename) {
+        Ok(input) => {
+            validate_input(puzzle, &input).map_err(|err| {
+                AocError::InvalidInput(format!(
+                    "{} is a bad download ({}), delete it to fetch it again",
+                    input_filename, err
+                ))
+            })?;
+            verify_checksum(&checksum_filename, &input_filename, &input)?;
+            Ok(input)
+        }
+        Err(_) => {
+            println!(
+                "Couldn't find input file {}, fetching from adventofcode.com",
+                input_filename
+            );
+            let fetched_input = fetch_input(puzzle)?;
+            fs::create_dir_all(&data_folder)?;
+            fs::write(&input_filename, &fetched_input)?;
+            write_checksum(&checksum_filename, &fetched_input)?;
+            Ok(fetched_input)
+        }
+    }
 }
 
-pub fn get_examples(year: u32, day: u32, part: u32) -> SimpleResult<Vec<(String, String)>> {
-    assert!((2015..3000).contains(&year));
-    assert!((1..=25).contains(&day));
-    assert!((1..=2).contains(&part));
-    let data_folder = format!("./data/{}/{}", year, day);
-    let examples_filename = format!("{}/examples_part{}.json", data_folder, part);
+pub fn get_examples(puzzle: PuzzleId) -> AocResult<Vec<(String, String)>> {
+    let data_folder = format!("./data/{}/{}", puzzle.year(), puzzle.day());
+    let examples_filename = format!("{}/examples_part{}.json", data_folder, puzzle.part());
     let examples_json =
-        fs::read_to_string(&examples_filename).or_else(|_| -> Result<String, Box<dyn Error>> {
+        fs::read_to_string(&examples_filename).or_else(|_| -> AocResult<String> {
             println!(
                 "Couldn't find examples file {}, fetching from adventofcode.com",
                 examples_filename
             );
-            let fetched_examples = fetch_examples(year, day, part)?;
+            let fetched_examples = fetch_examples(puzzle)?;
             let examples_str = serde_json::to_string(&fetched_examples)?;
             fs::create_dir_all(&data_folder)?;
             fs::write(examples_filename, &examples_str)?;
@@ -59,39 +83,94 @@ pub fn get_examples(year: u32, day: u32, part: u32) -> SimpleResult<Vec<(String,
     )?)
 }
 
-pub fn submit_answer(year: u32, day: u32, part: u32, answer: &str) -> SimpleResult<()> {
-    assert!((2015..3000).contains(&year));
-    assert!((1..=25).contains(&day));
-    assert!((1..=2).contains(&part));
-
-    if check_answer(year, day, part, answer)? {
-        println!(
-            "Correct answer submitted for {} day {} part {}: `{}`!",
-            year, day, part, answer
-        );
+pub fn submit_answer(puzzle: PuzzleId, answer: &str) -> AocResult<()> {
+    if check_answer(puzzle, answer)? {
+        println!("Correct answer submitted for {}: `{}`!", puzzle, answer);
     } else {
-        println!(
-            "Incorrect answer submitted for {} day {} part {}: `{}`",
-            year, day, part, answer
-        );
+        println!("Incorrect answer submitted for {}: `{}`", puzzle, answer);
     }
     Ok(())
 }
 
-fn fetch_input(year: u32, day: u32) -> SimpleResult<String> {
-    let url_path = format!("{}/day/{}/input", year, day);
-    let response = aoc_request(url_path)?;
-    assert!(
-        !response.starts_with("Please don't"),
-        "{} day {} has no input",
-        year,
-        day
+pub fn dry_run_submission(puzzle: PuzzleId, answer: &str) -> AocResult<()> {
+    println!(
+        "Dry run for {}: `{}` {}",
+        puzzle,
+        answer,
+        dry_run_answer(puzzle, answer)?
     );
+    Ok(())
+}
+
+pub fn examples_passed(puzzle: PuzzleId) -> bool {
+    fs::metadata(examples_passed_filename(puzzle)).is_ok()
+}
+
+pub fn record_examples_passed(puzzle: PuzzleId) -> AocResult<()> {
+    fs::write(examples_passed_filename(puzzle), Utc::now().to_rfc3339())?;
+    Ok(())
+}
+
+fn examples_passed_filename(puzzle: PuzzleId) -> String {
+    format!(
+        "./data/{}/{}/examples_passed_part{}.txt",
+        puzzle.year(),
+        puzzle.day(),
+        puzzle.part()
+    )
+}
+
+pub fn get_private_leaderboard(year: u32, id: &str) -> AocResult<Leaderboard> {
+    check_year(year)?;
+    let data_folder = format!("./data/{}", year);
+    let leaderboard_filename = format!("{}/leaderboard_{}.json", data_folder, id);
+    let cache_age = fs::metadata(&leaderboard_filename)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .and_then(|age| Duration::from_std(age).ok());
+    // adventofcode.com asks that private leaderboards are fetched at most once every 15 minutes
+    let leaderboard_json = match cache_age {
+        Some(age) if age < Duration::minutes(15) => fs::read_to_string(&leaderboard_filename)?,
+        _ => {
+            println!(
+                "Leaderboard cache {} is missing or stale, fetching from adventofcode.com",
+                leaderboard_filename
+            );
+            match fetch_leaderboard(year, id) {
+                Ok(fetched_leaderboard) => {
+                    fs::create_dir_all(&data_folder)?;
+                    fs::write(&leaderboard_filename, &fetched_leaderboard)?;
+                    fetched_leaderboard
+                }
+                // an out of date leaderboard is still better than none
+                Err(err) => match fs::read_to_string(&leaderboard_filename) {
+                    Ok(stale_leaderboard) => {
+                        println!(
+                            "Warning: couldn't fetch the leaderboard ({}), showing the stale cache {}",
+                            err, leaderboard_filename
+                        );
+                        stale_leaderboard
+                    }
+                    Err(_) => return Err(err),
+                },
+            }
+        }
+    };
+    parse_leaderboard(&leaderboard_json)
+}
+
+fn fetch_input(puzzle: PuzzleId) -> AocResult<String> {
+    puzzle.check_unlocked()?;
+    let url_path = format!("{}/day/{}/input", puzzle.year(), puzzle.day());
+    let response = aoc_puzzle_request(url_path)?;
+    validate_download(puzzle, &response)?;
     Ok(response)
 }
 
 #[test]
 fn test_fetch_input() {
-    fetch_input(2018, 1).unwrap();
-    get_input(2018, 1).unwrap();
+    let puzzle = PuzzleId::new(2018, 1, 1).unwrap();
+    fetch_input(puzzle).unwrap();
+    get_input(puzzle).unwrap();
 }
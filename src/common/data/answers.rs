@@ -1,36 +1,131 @@
-use std::{error::Error, fs};
+use std::{fmt::Display, fs};
 
 use kuchiki::{parse_html, traits::TendrilSink};
+use serde::{Deserialize, Serialize};
 
-use crate::common::{data::req::aoc_request, SimpleResult};
-
-use super::req::post_answer;
-
-pub fn check_answer(year: u32, day: u32, part: u32, answer: &str) -> SimpleResult<bool> {
-    Ok(if answer_is_known_incorrect(year, day, part, answer)? {
-        false
-    } else if let Some(correct_answer) = fetch_correct_answer(year, day, part)? {
-        println!(
-            "Puzzle already solved, correct answer was `{}`",
-            correct_answer
-        );
-        correct_answer == answer
-    } else if post_answer(year, day, part, answer)? {
-        true
-    } else {
-        write_answer_incorrect(year, day, part, answer)?;
-        false
+use crate::common::{data::req::aoc_puzzle_request, error::AocResult, puzzle::PuzzleId};
+
+use super::req::{post_answer, AnswerResponse};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AnswerBounds {
+    too_high: Option<i64>,
+    too_low: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DryRunDecision {
+    KnownCorrect,
+    KnownWrong(String),
+    KnownIncorrect,
+    TooHigh(i64),
+    TooLow(i64),
+    WouldSubmit,
+}
+
+impl Display for DryRunDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DryRunDecision::KnownCorrect => write!(f, "matches the known correct answer"),
+            DryRunDecision::KnownWrong(correct) => write!(
+                f,
+                "would reject as known-wrong, the correct answer is `{}`",
+                correct
+            ),
+            DryRunDecision::KnownIncorrect => {
+                write!(f, "would reject as known-wrong, it was submitted before")
+            }
+            DryRunDecision::TooHigh(bound) => write!(
+                f,
+                "would reject as too high, `{}` was already too high",
+                bound
+            ),
+            DryRunDecision::TooLow(bound) => write!(
+                f,
+                "would reject as too low, `{}` was already too low",
+                bound
+            ),
+            DryRunDecision::WouldSubmit => write!(f, "would submit"),
+        }
+    }
+}
+
+pub fn check_answer(puzzle: PuzzleId, answer: &str) -> AocResult<bool> {
+    Ok(match dry_run_answer(puzzle, answer)? {
+        DryRunDecision::KnownCorrect => true,
+        DryRunDecision::WouldSubmit => {
+            if let Some(correct_answer) = fetch_correct_answer(puzzle)? {
+                println!(
+                    "Puzzle already solved, correct answer was `{}`",
+                    correct_answer
+                );
+                write_correct_answer(puzzle, &correct_answer)?;
+                correct_answer == answer
+            } else {
+                let response = post_answer(puzzle, answer)?;
+                match response {
+                    AnswerResponse::Correct => write_correct_answer(puzzle, answer)?,
+                    AnswerResponse::Incorrect => write_answer_incorrect(puzzle, answer)?,
+                    AnswerResponse::TooHigh | AnswerResponse::TooLow => {
+                        write_answer_incorrect(puzzle, answer)?;
+                        write_answer_bound(puzzle, answer, response)?;
+                    }
+                }
+                response == AnswerResponse::Correct
+            }
+        }
+        decision => {
+            println!("Not submitting `{}`: {}", answer, decision);
+            false
+        }
     })
 }
 
-fn answer_is_known_incorrect(year: u32, day: u32, part: u32, answer: &str) -> SimpleResult<bool> {
-    let known_incorrect_answers = read_incorrect_answers(year, day, part)?;
-    Ok(known_incorrect_answers.contains(&answer.to_owned()))
+pub fn dry_run_answer(puzzle: PuzzleId, answer: &str) -> AocResult<DryRunDecision> {
+    Ok(decide_submission(
+        answer,
+        read_correct_answer(puzzle)?.as_deref(),
+        &read_incorrect_answers(puzzle)?,
+        &read_answer_bounds(puzzle)?,
+    ))
 }
 
-fn fetch_correct_answer(year: u32, day: u32, part: u32) -> SimpleResult<Option<String>> {
-    let url_path = format!("{}/day/{}", year, day);
-    let response = aoc_request(url_path)?;
+fn decide_submission(
+    answer: &str,
+    correct_answer: Option<&str>,
+    incorrect_answers: &[String],
+    bounds: &AnswerBounds,
+) -> DryRunDecision {
+    let numeric_answer = answer.parse::<i64>().ok();
+    if let Some(correct_answer) = correct_answer {
+        if correct_answer == answer {
+            DryRunDecision::KnownCorrect
+        } else {
+            DryRunDecision::KnownWrong(correct_answer.to_owned())
+        }
+    } else if incorrect_answers
+        .iter()
+        .any(|incorrect| incorrect == answer)
+    {
+        DryRunDecision::KnownIncorrect
+    } else if let Some(too_high) = bounds
+        .too_high
+        .filter(|too_high| numeric_answer.is_some_and(|n| n >= *too_high))
+    {
+        DryRunDecision::TooHigh(too_high)
+    } else if let Some(too_low) = bounds
+        .too_low
+        .filter(|too_low| numeric_answer.is_some_and(|n| n <= *too_low))
+    {
+        DryRunDecision::TooLow(too_low)
+    } else {
+        DryRunDecision::WouldSubmit
+    }
+}
+
+fn fetch_correct_answer(puzzle: PuzzleId) -> AocResult<Option<String>> {
+    let url_path = format!("{}/day/{}", puzzle.year(), puzzle.day());
+    let response = aoc_puzzle_request(url_path)?;
     let html = parse_html().one(response);
     let answer = html
         .select("main > p")
@@ -40,7 +135,7 @@ fn fetch_correct_answer(year: u32, day: u32, part: u32) -> SimpleResult<Option<S
                 .text_contents()
                 .starts_with("Your puzzle answer was")
         })
-        .nth((part - 1) as usize)
+        .nth((puzzle.part() - 1) as usize)
         .map(|p| {
             let code = p.as_node().select("code").unwrap().next().unwrap();
             code.text_contents().trim().to_owned()
@@ -48,58 +143,164 @@ fn fetch_correct_answer(year: u32, day: u32, part: u32) -> SimpleResult<Option<S
     Ok(answer)
 }
 
-fn read_incorrect_answers(year: u32, day: u32, part: u32) -> SimpleResult<Vec<String>> {
-    let data_folder = data_folder(year, day);
-    let incorrects_filename = incorrect_answers_filename(year, day, part);
-    let contents = fs::read_to_string(&incorrects_filename).or_else(
-        |_| -> Result<String, Box<dyn Error>> {
-            fs::create_dir_all(&data_folder)?;
-            let no_incorrects: Vec<String> = vec![];
-            let contents = serde_json::to_string(&no_incorrects)?;
-            fs::write(&incorrects_filename, &contents)?;
-            Ok(contents)
-        },
-    )?;
+fn read_correct_answer(puzzle: PuzzleId) -> AocResult<Option<String>> {
+    Ok(fs::read_to_string(correct_answer_filename(puzzle))
+        .ok()
+        .map(|answer| answer.trim().to_owned()))
+}
+
+fn write_correct_answer(puzzle: PuzzleId, answer: &str) -> AocResult<()> {
+    fs::create_dir_all(data_folder(puzzle))?;
+    fs::write(correct_answer_filename(puzzle), answer)?;
+    Ok(())
+}
+
+fn read_incorrect_answers(puzzle: PuzzleId) -> AocResult<Vec<String>> {
+    let data_folder = data_folder(puzzle);
+    let incorrects_filename = incorrect_answers_filename(puzzle);
+    let contents = fs::read_to_string(&incorrects_filename).or_else(|_| -> AocResult<String> {
+        fs::create_dir_all(&data_folder)?;
+        let no_incorrects: Vec<String> = vec![];
+        let contents = serde_json::to_string(&no_incorrects)?;
+        fs::write(&incorrects_filename, &contents)?;
+        Ok(contents)
+    })?;
     Ok(serde_json::from_str::<Vec<String>>(&contents)?)
 }
 
-fn write_answer_incorrect(year: u32, day: u32, part: u32, answer: &str) -> SimpleResult<()> {
-    let mut incorrect_answers = read_incorrect_answers(year, day, part)?;
+fn write_answer_incorrect(puzzle: PuzzleId, answer: &str) -> AocResult<()> {
+    let mut incorrect_answers = read_incorrect_answers(puzzle)?;
     incorrect_answers.push(answer.to_owned());
     fs::write(
-        incorrect_answers_filename(year, day, part),
+        incorrect_answers_filename(puzzle),
         serde_json::to_string(&incorrect_answers)?,
     )?;
     Ok(())
 }
 
-fn data_folder(year: u32, day: u32) -> String {
-    format!("./data/{}/{}", year, day)
+fn read_answer_bounds(puzzle: PuzzleId) -> AocResult<AnswerBounds> {
+    match fs::read_to_string(answer_bounds_filename(puzzle)) {
+        Ok(contents) => Ok(serde_json::from_str::<AnswerBounds>(&contents)?),
+        Err(_) => Ok(AnswerBounds::default()),
+    }
+}
+
+fn write_answer_bound(puzzle: PuzzleId, answer: &str, response: AnswerResponse) -> AocResult<()> {
+    let Ok(answer) = answer.parse::<i64>() else {
+        return Ok(());
+    };
+    let mut bounds = read_answer_bounds(puzzle)?;
+    match response {
+        AnswerResponse::TooHigh => {
+            bounds.too_high = Some(bounds.too_high.map_or(answer, |bound| bound.min(answer)))
+        }
+        AnswerResponse::TooLow => {
+            bounds.too_low = Some(bounds.too_low.map_or(answer, |bound| bound.max(answer)))
+        }
+        AnswerResponse::Correct | AnswerResponse::Incorrect => {}
+    }
+    fs::create_dir_all(data_folder(puzzle))?;
+    fs::write(
+        answer_bounds_filename(puzzle),
+        serde_json::to_string(&bounds)?,
+    )?;
+    Ok(())
+}
+
+fn data_folder(puzzle: PuzzleId) -> String {
+    format!("./data/{}/{}", puzzle.year(), puzzle.day())
+}
+
+fn correct_answer_filename(puzzle: PuzzleId) -> String {
+    format!("{}/correct_part{}.txt", data_folder(puzzle), puzzle.part())
+}
+
+fn incorrect_answers_filename(puzzle: PuzzleId) -> String {
+    format!(
+        "{}/incorrect_part{}.json",
+        data_folder(puzzle),
+        puzzle.part()
+    )
 }
 
-fn incorrect_answers_filename(year: u32, day: u32, part: u32) -> String {
-    format!("{}/incorrect_part{}.json", data_folder(year, day), part)
+fn answer_bounds_filename(puzzle: PuzzleId) -> String {
+    format!("{}/bounds_part{}.json", data_folder(puzzle), puzzle.part())
 }
 
 #[test]
-fn test_incorrect_answer() -> SimpleResult<()> {
-    write_answer_incorrect(2018, 1, 1, "0")?;
-    assert!(answer_is_known_incorrect(2018, 1, 1, "0")?);
+fn test_incorrect_answer() -> AocResult<()> {
+    let puzzle = PuzzleId::new(2018, 1, 1)?;
+    write_answer_incorrect(puzzle, "0")?;
+    assert!(read_incorrect_answers(puzzle)?.contains(&"0".to_owned()));
     Ok(())
 }
 
 #[test]
-fn test_correct_answer() -> SimpleResult<()> {
+fn test_decide_submission() {
+    let no_bounds = AnswerBounds::default();
+    let bounds = AnswerBounds {
+        too_high: Some(100),
+        too_low: Some(10),
+    };
+    let incorrect = vec!["42".to_owned()];
+    assert_eq!(
+        decide_submission("50", None, &[], &no_bounds),
+        DryRunDecision::WouldSubmit
+    );
+    assert_eq!(
+        decide_submission("50", Some("50"), &incorrect, &bounds),
+        DryRunDecision::KnownCorrect
+    );
+    assert_eq!(
+        decide_submission("51", Some("50"), &incorrect, &bounds),
+        DryRunDecision::KnownWrong("50".to_owned())
+    );
+    assert_eq!(
+        decide_submission("42", None, &incorrect, &bounds),
+        DryRunDecision::KnownIncorrect
+    );
+    assert_eq!(
+        decide_submission("100", None, &incorrect, &bounds),
+        DryRunDecision::TooHigh(100)
+    );
+    assert_eq!(
+        decide_submission("10", None, &incorrect, &bounds),
+        DryRunDecision::TooLow(10)
+    );
+    assert_eq!(
+        decide_submission("99", None, &incorrect, &bounds),
+        DryRunDecision::WouldSubmit
+    );
+    assert_eq!(
+        decide_submission("abc", None, &incorrect, &bounds),
+        DryRunDecision::WouldSubmit
+    );
+}
+
+#[test]
+fn test_correct_answer() -> AocResult<()> {
     // Note: this test assumes 2018 is complete up until day 3 part 1
-    assert_eq!(fetch_correct_answer(2018, 1, 1)?, Some("582".to_owned()));
-    assert_eq!(fetch_correct_answer(2018, 1, 2)?, Some("488".to_owned()));
-    assert_eq!(fetch_correct_answer(2018, 2, 1)?, Some("4980".to_owned()));
     assert_eq!(
-        fetch_correct_answer(2018, 2, 2)?,
+        fetch_correct_answer(PuzzleId::new(2018, 1, 1)?)?,
+        Some("582".to_owned())
+    );
+    assert_eq!(
+        fetch_correct_answer(PuzzleId::new(2018, 1, 2)?)?,
+        Some("488".to_owned())
+    );
+    assert_eq!(
+        fetch_correct_answer(PuzzleId::new(2018, 2, 1)?)?,
+        Some("4980".to_owned())
+    );
+    assert_eq!(
+        fetch_correct_answer(PuzzleId::new(2018, 2, 2)?)?,
         Some("qysdtrkloagnfozuwujmhrbvx".to_owned())
     );
-    assert_eq!(fetch_correct_answer(2018, 3, 1)?, Some("101469".to_owned()));
-    assert_eq!(fetch_correct_answer(2018, 3, 2)?, None);
-    assert_eq!(fetch_correct_answer(2018, 4, 1)?, None);
+    assert_eq!(
+        fetch_correct_answer(PuzzleId::new(2018, 3, 1)?)?,
+        Some("101469".to_owned())
+    );
+    assert_eq!(fetch_correct_answer(PuzzleId::new(2018, 3, 2)?)?, None);
+    assert_eq!(fetch_correct_answer(PuzzleId::new(2018, 4, 1)?)?, None);
     Ok(())
 }
@@ -3,19 +3,20 @@ use std::io::{self, BufRead};
 use kuchiki::{parse_html, traits::TendrilSink};
 
 use crate::common::{
-    data::{get_examples, req::aoc_request},
-    SimpleResult,
+    data::{get_examples, req::aoc_puzzle_request},
+    error::{AocError, AocResult},
+    puzzle::PuzzleId,
 };
 
-pub fn fetch_examples(year: u32, day: u32, part: u32) -> SimpleResult<Vec<(String, String)>> {
-    let url_path = format!("{}/day/{}", year, day);
-    let response = aoc_request(url_path)?;
+pub fn fetch_examples(puzzle: PuzzleId) -> AocResult<Vec<(String, String)>> {
+    let url_path = format!("{}/day/{}", puzzle.year(), puzzle.day());
+    let response = aoc_puzzle_request(url_path)?;
     let html = parse_html().one(response);
     let example_candidates = html
         .select("article.day-desc")
         .unwrap()
-        .nth((part - 1) as usize)
-        .unwrap_or_else(|| panic!("Day {} part {} is not available", day, part))
+        .nth((puzzle.part() - 1) as usize)
+        .ok_or_else(|| AocError::NotUnlocked(puzzle.to_string()))?
         .as_node()
         .select("code")
         .unwrap()
@@ -31,8 +32,8 @@ pub fn fetch_examples(year: u32, day: u32, part: u32) -> SimpleResult<Vec<(Strin
         pre_tag_count,
         example_candidates.len()
     );
-    if part == 2 {
-        let part1_examples = get_examples(year, day, 1)?;
+    if puzzle.part() == 2 {
+        let part1_examples = get_examples(puzzle.with_part(1)?)?;
         for (example, _) in part1_examples {
             if !example_candidates.contains(&example) {
                 example_candidates.push(example);
@@ -43,7 +44,10 @@ pub fn fetch_examples(year: u32, day: u32, part: u32) -> SimpleResult<Vec<(Strin
     for content in example_candidates {
         println!("Possible example found:\n{}\nIf this is an example, paste the corresponding correct answer. Else, press 'Enter':", content);
         let mut line = String::new();
-        io::stdin().lock().read_line(&mut line)?;
+        io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(AocError::Prompt)?;
         let line = line.trim().to_owned();
         if !line.is_empty() {
             examples.push((content, line));
@@ -0,0 +1,125 @@
+use std::fs;
+
+use crate::common::{
+    error::{AocError, AocResult},
+    puzzle::PuzzleId,
+};
+
+pub fn validate_input(puzzle: PuzzleId, input: &str) -> AocResult<()> {
+    if input.trim().is_empty() {
+        Err(AocError::InvalidInput(format!(
+            "{} day {} input is empty",
+            puzzle.year(),
+            puzzle.day()
+        )))
+    } else if input.contains("Puzzle inputs differ by user") {
+        Err(AocError::AuthExpired)
+    } else if input.contains("Please don't repeatedly request this endpoint") {
+        Err(AocError::NotUnlocked(format!(
+            "{} day {}",
+            puzzle.year(),
+            puzzle.day()
+        )))
+    } else if input.contains("<!DOCTYPE") || input.contains("<html") {
+        Err(AocError::InvalidInput(format!(
+            "{} day {} input looks like an HTML page",
+            puzzle.year(),
+            puzzle.day()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_download(puzzle: PuzzleId, input: &str) -> AocResult<()> {
+    validate_input(puzzle, input)?;
+    if !input.ends_with('\n') {
+        return Err(AocError::InvalidInput(format!(
+            "{} day {} input does not end with a newline, the download was probably truncated",
+            puzzle.year(),
+            puzzle.day()
+        )));
+    }
+    Ok(())
+}
+
+// FNV-1a, which unlike `DefaultHasher` is stable across Rust versions
+pub fn checksum(input: &[u8]) -> String {
+    let hash = input.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+pub fn write_checksum(checksum_filename: &str, input: &str) -> AocResult<()> {
+    fs::write(checksum_filename, checksum(input.as_bytes()))?;
+    Ok(())
+}
+
+pub fn verify_checksum(
+    checksum_filename: &str,
+    input_filename: &str,
+    input: &str,
+) -> AocResult<()> {
+    let actual = checksum(input.as_bytes());
+    match fs::read_to_string(checksum_filename) {
+        Ok(expected) if expected.trim() != actual => println!(
+            "Warning: {} has changed since it was fetched (checksum `{}`, expected `{}`)",
+            input_filename,
+            actual,
+            expected.trim()
+        ),
+        Ok(_) => {}
+        Err(_) => write_checksum(checksum_filename, input)?,
+    }
+    Ok(())
+}
+
+pub fn normalize_input(raw: &str) -> String {
+    let mut normalized = raw.replace("\r\n", "\n").replace('\r', "\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    normalized.push('\n');
+    normalized
+}
+
+#[test]
+fn test_validate_input() -> AocResult<()> {
+    let puzzle = PuzzleId::new(2018, 1, 1)?;
+    assert!(validate_input(puzzle, "+1\n-2\n").is_ok());
+    assert!(validate_input(puzzle, "").is_err());
+    assert!(validate_input(puzzle, "+1\n-2").is_ok());
+    assert!(validate_download(puzzle, "+1\n-2\n").is_ok());
+    assert!(validate_download(puzzle, "+1\n-").is_err());
+    assert!(matches!(
+        validate_input(
+            puzzle,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ),
+        Err(AocError::AuthExpired)
+    ));
+    assert!(matches!(
+        validate_input(
+            puzzle,
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+        ),
+        Err(AocError::NotUnlocked(_))
+    ));
+    assert!(validate_input(puzzle, "<!DOCTYPE html>\n<html></html>\n").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_checksum() {
+    assert_eq!(checksum(b""), "cbf29ce484222325");
+    assert_eq!(checksum(b"a"), "af63dc4c8601ec8c");
+    assert_ne!(checksum(b"+1\n-2\n"), checksum(b"+1\r\n-2\r\n"));
+}
+
+#[test]
+fn test_normalize_input() {
+    assert_eq!(normalize_input("a\r\nb\r\n"), "a\nb\n");
+    assert_eq!(normalize_input("a\nb"), "a\nb\n");
+    assert_eq!(normalize_input("a\nb\n\n\n"), "a\nb\n");
+    assert_eq!(normalize_input("a\n\nb\n"), "a\n\nb\n");
+    assert_eq!(normalize_input("a\rb\r"), "a\nb\n");
+}
@@ -0,0 +1,277 @@
+use std::{collections::HashMap, fmt::Display};
+
+use chrono::{DateTime, Duration, FixedOffset};
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::common::{data::req::aoc_request, error::AocResult};
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    pub members: HashMap<String, LeaderboardMember>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardMember {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, StarCompletion>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StarCompletion {
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    pub fn ranked_members(&self) -> Vec<&LeaderboardMember> {
+        self.members
+            .values()
+            .sorted_by(|a, b| {
+                b.local_score
+                    .cmp(&a.local_score)
+                    .then(b.stars.cmp(&a.stars))
+                    .then(a.last_star_ts.cmp(&b.last_star_ts))
+                    .then(a.id.cmp(&b.id))
+            })
+            .collect_vec()
+    }
+}
+
+impl LeaderboardMember {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star_ts(&self, day: u32, part: u32) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())
+            .and_then(|parts| parts.get(&part.to_string()))
+            .map(|star| star.get_star_ts)
+    }
+
+    pub fn stars_on_day(&self, day: u32) -> u32 {
+        (1..=2)
+            .filter(|part| self.star_ts(day, *part).is_some())
+            .count() as u32
+    }
+
+    pub fn part_delta(&self, day: u32) -> Option<Duration> {
+        Some(Duration::seconds(
+            self.star_ts(day, 2)? - self.star_ts(day, 1)?,
+        ))
+    }
+
+    fn completed_days(&self) -> Vec<u32> {
+        self.completion_day_level
+            .keys()
+            .filter_map(|day| day.parse().ok())
+            .sorted()
+            .collect_vec()
+    }
+}
+
+pub fn fetch_leaderboard(year: u32, id: &str) -> AocResult<String> {
+    let url_path = format!("{}/leaderboard/private/view/{}.json", year, id);
+    let response = aoc_request(url_path)?;
+    parse_leaderboard(&response)?;
+    Ok(response)
+}
+
+pub fn parse_leaderboard(json: &str) -> AocResult<Leaderboard> {
+    Ok(serde_json::from_str::<Leaderboard>(json)?)
+}
+
+fn format_timestamp(ts: i64) -> String {
+    // Puzzles unlock at midnight EST, so show completion times in that zone.
+    let est = FixedOffset::west_opt(5 * 3600).unwrap();
+    DateTime::from_timestamp(ts, 0)
+        .map(|time| time.with_timezone(&est))
+        .map(|time| time.format("%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| ts.to_string())
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+    if seconds >= 3600 {
+        format!(
+            "{}h{:02}m{:02}s",
+            seconds / 3600,
+            (seconds % 3600) / 60,
+            seconds % 60
+        )
+    } else if seconds >= 60 {
+        format!("{}m{:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+impl Display for Leaderboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let members = self.ranked_members();
+        let days = members
+            .iter()
+            .flat_map(|member| member.completed_days())
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "Private leaderboard {} ({}), {} members",
+            self.owner_id,
+            self.event,
+            members.len()
+        )?;
+        write!(f, "{:>4} {:>5} {:>5}  ", "", "score", "stars")?;
+        for day in 1..=days {
+            write!(f, "{}", day % 10)?;
+        }
+        writeln!(f)?;
+        for (rank, member) in members.iter().enumerate() {
+            write!(
+                f,
+                "{:>3}) {:>5} {:>5}  ",
+                rank + 1,
+                member.local_score,
+                member.stars
+            )?;
+            for day in 1..=days {
+                f.write_str(match member.stars_on_day(day) {
+                    2 => "*",
+                    1 => "+",
+                    _ => ".",
+                })?;
+            }
+            writeln!(f, "  {}", member.display_name())?;
+        }
+        for member in members {
+            let completed_days = member.completed_days();
+            if completed_days.is_empty() {
+                continue;
+            }
+            writeln!(f)?;
+            writeln!(f, "{}", member.display_name())?;
+            for day in completed_days {
+                let part1 = member
+                    .star_ts(day, 1)
+                    .map(format_timestamp)
+                    .unwrap_or_else(|| "-".to_string());
+                let part2 = member
+                    .star_ts(day, 2)
+                    .map(format_timestamp)
+                    .unwrap_or_else(|| "-".to_string());
+                let delta = member
+                    .part_delta(day)
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string());
+                writeln!(
+                    f,
+                    "  day {:>2}: part 1 {:>14}  part 2 {:>14}  delta {:>9}",
+                    day, part1, part2, delta
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+const TEST_LEADERBOARD: &str = r#"{
+    "owner_id": 100,
+    "event": "2023",
+    "members": {
+        "100": {
+            "id": 100,
+            "name": "Alice",
+            "stars": 3,
+            "local_score": 7,
+            "global_score": 0,
+            "last_star_ts": 1701410000,
+            "completion_day_level": {
+                "1": {
+                    "1": {"get_star_ts": 1701407100, "star_index": 10},
+                    "2": {"get_star_ts": 1701407548, "star_index": 20}
+                },
+                "2": {
+                    "1": {"get_star_ts": 1701410000, "star_index": 30}
+                }
+            }
+        },
+        "200": {
+            "id": 200,
+            "name": null,
+            "stars": 2,
+            "local_score": 4,
+            "global_score": 0,
+            "last_star_ts": 1701420000,
+            "completion_day_level": {
+                "1": {
+                    "1": {"get_star_ts": 1701408000, "star_index": 15},
+                    "2": {"get_star_ts": 1701420000, "star_index": 40}
+                }
+            }
+        },
+        "300": {
+            "id": 300,
+            "name": "Carol",
+            "stars": 0,
+            "local_score": 0,
+            "global_score": 0,
+            "last_star_ts": 0,
+            "completion_day_level": {}
+        }
+    }
+}"#;
+
+#[test]
+fn test_parse_leaderboard() -> AocResult<()> {
+    let leaderboard = parse_leaderboard(TEST_LEADERBOARD)?;
+    assert_eq!(leaderboard.owner_id, 100);
+    assert_eq!(leaderboard.event, "2023");
+    assert_eq!(leaderboard.members.len(), 3);
+    let alice = &leaderboard.members["100"];
+    assert_eq!(alice.star_ts(1, 2), Some(1701407548));
+    assert_eq!(alice.star_ts(2, 2), None);
+    assert_eq!(alice.stars_on_day(1), 2);
+    assert_eq!(alice.stars_on_day(2), 1);
+    assert_eq!(alice.part_delta(1), Some(Duration::seconds(448)));
+    assert_eq!(alice.part_delta(2), None);
+    assert_eq!(
+        leaderboard.members["200"].display_name(),
+        "(anonymous user #200)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_leaderboard_ranking() -> AocResult<()> {
+    let leaderboard = parse_leaderboard(TEST_LEADERBOARD)?;
+    let ranked_ids = leaderboard
+        .ranked_members()
+        .iter()
+        .map(|member| member.id)
+        .collect_vec();
+    assert_eq!(ranked_ids, vec![100, 200, 300]);
+    Ok(())
+}
+
+#[test]
+fn test_leaderboard_display() -> AocResult<()> {
+    let leaderboard = parse_leaderboard(TEST_LEADERBOARD)?;
+    let output = leaderboard.to_string();
+    assert!(output.contains("  1)     7     3  *+  Alice"));
+    assert!(output.contains("  2)     4     2  *.  (anonymous user #200)"));
+    assert!(
+        output.contains("  day  1: part 1 12-01 00:05:00  part 2 12-01 00:12:28  delta     7m28s")
+    );
+    assert!(output.contains("delta  3h20m00s"));
+    Ok(())
+}
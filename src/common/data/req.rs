@@ -7,25 +7,61 @@ use std::{
 use chrono::{DateTime, Duration, Utc};
 use ureq::{Cookie, Request};
 
-use crate::common::SimpleResult;
+use crate::common::{
+    error::{AocError, AocResult},
+    puzzle::PuzzleId,
+};
 
-pub fn aoc_request(path: String) -> SimpleResult<String> {
+pub fn aoc_request(path: String) -> AocResult<String> {
     let url = format!("https://adventofcode.com/{}", path);
     let response = set_headers(ureq::get(&url))?.call()?;
-    let response_text = response.into_string()?;
-    Ok(response_text)
+    read_body(response)
+}
+
+// puzzle pages and inputs are only missing before the puzzle unlocks
+pub fn aoc_puzzle_request(path: String) -> AocResult<String> {
+    aoc_request(path).map_err(not_found_as_locked)
+}
+
+fn not_found_as_locked(err: AocError) -> AocError {
+    match err {
+        AocError::NotFound(url) => AocError::NotUnlocked(url),
+        err => err,
+    }
+}
+
+fn read_body(response: ureq::Response) -> AocResult<String> {
+    response.into_string().map_err(AocError::ResponseBody)
 }
 
-pub fn post_answer(year: u32, day: u32, part: u32, answer: &str) -> SimpleResult<bool> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerResponse {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+}
+
+pub fn post_answer(puzzle: PuzzleId, answer: &str) -> AocResult<AnswerResponse> {
     check_one_minute_between_submissions()?;
-    let url = format!("https://adventofcode.com/{}/day/{}/answer", year, day);
-    let level = part.to_string();
+    let url = format!(
+        "https://adventofcode.com/{}/day/{}/answer",
+        puzzle.year(),
+        puzzle.day()
+    );
+    let level = puzzle.part().to_string();
     let form_body: Vec<(&str, &str)> = vec![("level", &level), ("answer", answer)];
     println!("Posting answer `{}` to {}", answer, url);
-    let response = set_headers(ureq::post(&url))?.send_form(&form_body)?;
-    let response_text = response.into_string()?;
+    let response = set_headers(ureq::post(&url))?
+        .send_form(&form_body)
+        .map_err(|err| not_found_as_locked(err.into()))?;
+    let response_text = read_body(response)?;
     if response_text.contains("That's the right answer") {
-        Ok(true)
+        Ok(AnswerResponse::Correct)
+    } else if response_text.contains("You gave an answer too recently") {
+        Err(AocError::RateLimited(
+            "answer submitted too recently".to_owned(),
+        ))
     } else if response_text.contains("That's not the right answer") {
         let message = response_text
             .split("That's not the right answer")
@@ -36,16 +72,19 @@ pub fn post_answer(year: u32, day: u32, part: u32, answer: &str) -> SimpleResult
         if !message.is_empty() {
             println!("Wrong: '{}'", message);
         }
-        Ok(false)
+        Ok(if message.contains("too high") {
+            AnswerResponse::TooHigh
+        } else if message.contains("too low") {
+            AnswerResponse::TooLow
+        } else {
+            AnswerResponse::Incorrect
+        })
     } else {
-        panic!(
-            "{}\n----\nSomething went wrong when submitting the answer. See above HTML output.",
-            response_text
-        );
+        Err(AocError::UnexpectedResponse(response_text))
     }
 }
 
-fn set_headers(request: Request) -> SimpleResult<Request> {
+fn set_headers(request: Request) -> AocResult<Request> {
     let cookie = Cookie::new("session", load_session_cookie()?);
     Ok(request.set("Cookie", &cookie.to_string()).set(
         "User-Agent",
@@ -53,10 +92,10 @@ fn set_headers(request: Request) -> SimpleResult<Request> {
     ))
 }
 
-fn check_one_minute_between_submissions() -> SimpleResult<()> {
+fn check_one_minute_between_submissions() -> AocResult<()> {
     let path = &"./data/last_submission_time.txt";
     let time_since_last_submission = fs::read_to_string(path)
-        .map(|contents| -> SimpleResult<Duration> {
+        .map(|contents| -> AocResult<Duration> {
             Ok(Utc::now().signed_duration_since(DateTime::parse_from_rfc3339(&contents)?))
         })
         .unwrap_or_else(|_| Ok(Duration::hours(1)))?;
@@ -67,20 +106,23 @@ fn check_one_minute_between_submissions() -> SimpleResult<()> {
             "Too short time between submissions, sleeping for {} seconds before next submission",
             remaining_time.num_seconds()
         );
-        sleep(remaining_time.to_std()?);
+        sleep(remaining_time.to_std().unwrap_or_default());
     }
     fs::write(path, Utc::now().to_rfc3339())?;
     Ok(())
 }
 
-fn load_session_cookie() -> SimpleResult<String> {
+fn load_session_cookie() -> AocResult<String> {
     let cookie_file_name = "./data/.session_cookie";
     fs::read_to_string(cookie_file_name)
         .map(|s| s.trim().to_string())
-        .or_else(|_err| -> SimpleResult<String> {
+        .or_else(|_err| -> AocResult<String> {
             println!("No session cookie found. Please log in to https://adventofcode.com/ in your browser, open the browser console, copy the value of the 'session' cookie, and paste it here:");
             let mut line = String::new();
-            io::stdin().lock().read_line(&mut line)?;
+            io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(AocError::Prompt)?;
             fs::create_dir("./data/")?;
             fs::write(cookie_file_name, &line)?;
             Ok(line.trim().to_string())
@@ -88,6 +130,20 @@ fn load_session_cookie() -> SimpleResult<String> {
 }
 
 #[test]
-fn check_one_minute() -> SimpleResult<()> {
+fn test_not_found() -> AocResult<()> {
+    let not_found = || {
+        let response = ureq::Response::new(404, "Not Found", "").unwrap();
+        AocError::from(ureq::Error::Status(404, response))
+    };
+    assert!(matches!(not_found(), AocError::NotFound(_)));
+    assert!(matches!(
+        not_found_as_locked(not_found()),
+        AocError::NotUnlocked(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn check_one_minute() -> AocResult<()> {
     check_one_minute_between_submissions()
 }
@@ -0,0 +1,59 @@
+use std::io;
+
+use thiserror::Error;
+
+pub type AocResult<T> = std::result::Result<T, AocError>;
+
+#[derive(Debug, Error)]
+pub enum AocError {
+    #[error("{year} day {day} part {part} is not a valid puzzle")]
+    InvalidPuzzle { year: u32, day: u32, part: u32 },
+    #[error("{0} is not unlocked yet")]
+    NotUnlocked(String),
+    #[error("Session cookie was rejected by adventofcode.com, delete ./data/.session_cookie and log in again")]
+    AuthExpired,
+    #[error("Rate limited by adventofcode.com: {0}")]
+    RateLimited(String),
+    #[error("Request to adventofcode.com failed: {0}")]
+    Network(Box<ureq::Error>),
+    #[error("Could not read the response from adventofcode.com: {0}")]
+    ResponseBody(io::Error),
+    #[error("{0} was not found on adventofcode.com")]
+    NotFound(String),
+    #[error("Could not read or write cached data: {0}")]
+    Cache(#[from] io::Error),
+    #[error("Could not read from stdin: {0}")]
+    Prompt(io::Error),
+    #[error("Failed to parse {0}")]
+    Parse(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Unexpected response from adventofcode.com:\n{0}")]
+    UnexpectedResponse(String),
+}
+
+impl From<ureq::Error> for AocError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(400 | 401 | 403, _) => AocError::AuthExpired,
+            // only the caller knows if this means a puzzle isn't out yet or something else
+            ureq::Error::Status(404, response) => AocError::NotFound(response.get_url().to_owned()),
+            ureq::Error::Status(429, response) => {
+                AocError::RateLimited(response.status_text().to_owned())
+            }
+            err => AocError::Network(Box::new(err)),
+        }
+    }
+}
+
+impl From<serde_json::Error> for AocError {
+    fn from(err: serde_json::Error) -> Self {
+        AocError::Parse(format!("JSON: {}", err))
+    }
+}
+
+impl From<chrono::ParseError> for AocError {
+    fn from(err: chrono::ParseError) -> Self {
+        AocError::Parse(format!("timestamp: {}", err))
+    }
+}
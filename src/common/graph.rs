@@ -0,0 +1,331 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Add,
+};
+
+use itertools::Itertools;
+use num::Zero;
+
+pub mod dot;
+
+// a directed graph between named nodes, each name gets a dense id the first time it's seen
+#[derive(Debug, Clone, Default)]
+pub struct Graph<'a> {
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new() -> Self {
+        Graph {
+            names: vec![],
+            ids: HashMap::new(),
+            successors: vec![],
+            predecessors: vec![],
+        }
+    }
+
+    pub fn intern(&mut self, name: &'a str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name);
+        self.ids.insert(name, id);
+        self.successors.push(vec![]);
+        self.predecessors.push(vec![]);
+        id
+    }
+
+    pub fn add_edge(&mut self, from: &'a str, to: &'a str) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.successors[from].push(to);
+        self.predecessors[to].push(from);
+    }
+
+    pub fn add_edges(&mut self, from: &'a str, to: impl IntoIterator<Item = &'a str>) {
+        self.intern(from);
+        for to in to {
+            self.add_edge(from, to);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &'a str {
+        self.names[id]
+    }
+
+    pub fn successors(&self, id: usize) -> &[usize] {
+        &self.successors[id]
+    }
+
+    pub fn predecessors(&self, id: usize) -> &[usize] {
+        &self.predecessors[id]
+    }
+
+    pub fn successor_names(&self, name: &str) -> Vec<&'a str> {
+        self.id(name)
+            .map_or(vec![], |id| self.names_of(&self.successors[id]))
+    }
+
+    pub fn predecessor_names(&self, name: &str) -> Vec<&'a str> {
+        self.id(name)
+            .map_or(vec![], |id| self.names_of(&self.predecessors[id]))
+    }
+
+    pub fn names_of(&self, ids: &[usize]) -> Vec<&'a str> {
+        ids.iter().map(|id| self.names[*id]).collect_vec()
+    }
+
+    // the number of edges on a shortest path between every pair of nodes, by id
+    pub fn bfs_all_pairs(&self) -> Vec<Vec<Option<usize>>> {
+        (0..self.len())
+            .map(|start| {
+                let mut distances = vec![None; self.len()];
+                distances[start] = Some(0);
+                let mut queue = VecDeque::from([start]);
+                while let Some(id) = queue.pop_front() {
+                    let distance = distances[id].unwrap() + 1;
+                    for next in &self.successors[id] {
+                        if distances[*next].is_none() {
+                            distances[*next] = Some(distance);
+                            queue.push_back(*next);
+                        }
+                    }
+                }
+                distances
+            })
+            .collect_vec()
+    }
+
+    // the cheapest path between every pair of nodes, by id, with `weight` giving the cost of each edge
+    pub fn floyd_warshall<W>(&self, weight: impl Fn(usize, usize) -> W) -> Vec<Vec<Option<W>>>
+    where
+        W: Copy + Ord + Zero + Add<Output = W>,
+    {
+        let mut distances = vec![vec![None; self.len()]; self.len()];
+        for (from, successors) in self.successors.iter().enumerate() {
+            distances[from][from] = Some(W::zero());
+            for to in successors {
+                let w = weight(from, *to);
+                if distances[from][*to].is_none_or(|d| w < d) {
+                    distances[from][*to] = Some(w);
+                }
+            }
+        }
+        for via in 0..self.len() {
+            let via_distances = distances[via].clone();
+            for from_distances in distances.iter_mut() {
+                let Some(first) = from_distances[via] else {
+                    continue;
+                };
+                for (distance, second) in from_distances.iter_mut().zip(&via_distances) {
+                    if let Some(second) = second {
+                        let total = first + *second;
+                        if distance.is_none_or(|d| total < d) {
+                            *distance = Some(total);
+                        }
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    // every node after all of its predecessors, or the ids along one cycle if there is one
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let mut in_degree = self.predecessors.iter().map(Vec::len).collect_vec();
+        let mut queue: VecDeque<usize> = (0..self.len()).filter(|id| in_degree[*id] == 0).collect();
+        let mut order = vec![];
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for next in &self.successors[id] {
+                in_degree[*next] -= 1;
+                if in_degree[*next] == 0 {
+                    queue.push_back(*next);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            return Ok(order);
+        }
+        // every node left over has a predecessor that's left over too, so walking back
+        // through those has to come round to a node seen before
+        let mut seen = vec![None; self.len()];
+        let mut walk = vec![];
+        let mut id = (0..self.len()).find(|id| in_degree[*id] > 0).unwrap();
+        while seen[id].is_none() {
+            seen[id] = Some(walk.len());
+            walk.push(id);
+            id = *self.predecessors[id]
+                .iter()
+                .find(|p| in_degree[**p] > 0)
+                .unwrap();
+        }
+        let mut cycle = walk.split_off(seen[id].unwrap());
+        cycle.reverse();
+        Err(cycle)
+    }
+
+    // Tarjan's algorithm, each component comes after every component it has an edge into
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: vec![None; self.len()],
+            low_link: vec![0; self.len()],
+            on_stack: vec![false; self.len()],
+            stack: vec![],
+            next_index: 0,
+            components: vec![],
+        };
+        for id in 0..self.len() {
+            if tarjan.index[id].is_none() {
+                tarjan.visit(id);
+            }
+        }
+        tarjan.components
+    }
+}
+
+struct Tarjan<'g, 'a> {
+    graph: &'g Graph<'a>,
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_, '_> {
+    fn visit(&mut self, id: usize) {
+        self.index[id] = Some(self.next_index);
+        self.low_link[id] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(id);
+        self.on_stack[id] = true;
+        for next in self.graph.successors(id) {
+            match self.index[*next] {
+                None => {
+                    self.visit(*next);
+                    self.low_link[id] = self.low_link[id].min(self.low_link[*next]);
+                }
+                Some(index) if self.on_stack[*next] => {
+                    self.low_link[id] = self.low_link[id].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+        if Some(self.low_link[id]) == self.index[id] {
+            let mut component = vec![];
+            loop {
+                let member = self.stack.pop().unwrap();
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == id {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_graph() -> Graph<'static> {
+    // a -> b -> c -> a is a cycle, d and e hang off it and f is on its own
+    let mut graph = Graph::new();
+    graph.add_edges("a", ["b"]);
+    graph.add_edges("b", ["c", "d"]);
+    graph.add_edges("c", ["a", "e"]);
+    graph.add_edges("d", ["e"]);
+    graph.add_edges("f", []);
+    graph
+}
+
+#[test]
+fn test_graph_queries() {
+    let graph = test_graph();
+    assert_eq!(graph.len(), 6);
+    assert_eq!(graph.name(graph.id("d").unwrap()), "d");
+    assert_eq!(graph.id("z"), None);
+    assert_eq!(graph.successor_names("b"), vec!["c", "d"]);
+    assert_eq!(graph.predecessor_names("e"), vec!["c", "d"]);
+    assert_eq!(graph.predecessor_names("f"), Vec::<&str>::new());
+
+    let id = |name| graph.id(name).unwrap();
+    assert_eq!(graph.predecessors(id("e")), &[id("c"), id("d")]);
+    assert_eq!(graph.successors(id("b")), &[id("c"), id("d")]);
+    assert!(graph.predecessors(id("f")).is_empty());
+    let bfs = graph.bfs_all_pairs();
+    assert_eq!(bfs[id("a")][id("e")], Some(3));
+    assert_eq!(bfs[id("e")][id("a")], None);
+    assert_eq!(bfs[id("c")][id("d")], Some(3));
+    // everything costs 1 except b -> d
+    let weighted = graph.floyd_warshall(|from, to| {
+        if (from, to) == (id("b"), id("d")) {
+            10
+        } else {
+            1
+        }
+    });
+    assert_eq!(weighted[id("a")][id("e")], Some(3));
+    assert_eq!(weighted[id("a")][id("d")], Some(11));
+    assert_eq!(weighted[id("f")][id("f")], Some(0));
+    let unweighted = graph.floyd_warshall(|_, _| 1);
+    for (from, to) in (0..graph.len()).cartesian_product(0..graph.len()) {
+        assert_eq!(unweighted[from][to], bfs[from][to]);
+    }
+}
+
+#[test]
+fn test_graph_ordering() {
+    let graph = test_graph();
+    let cycle = graph.topological_sort().unwrap_err();
+    let mut names = graph.names_of(&cycle);
+    assert_eq!(names.len(), 3);
+    for (from, to) in cycle.iter().circular_tuple_windows() {
+        assert!(graph.successors(*from).contains(to));
+    }
+    names.sort();
+    assert_eq!(names, vec!["a", "b", "c"]);
+
+    let components = graph
+        .strongly_connected_components()
+        .iter()
+        .map(|c| graph.names_of(c).into_iter().sorted().collect_vec())
+        .collect_vec();
+    assert_eq!(components.len(), 4);
+    assert!(components.contains(&vec!["a", "b", "c"]));
+    // reverse topological order, so e comes before d and d before the cycle
+    let position = |name| components.iter().position(|c| c.contains(&name)).unwrap();
+    assert!(position("e") < position("d") && position("d") < position("a"));
+
+    // without c -> a the graph is a DAG
+    let mut dag = Graph::new();
+    for from in ["a", "b", "c", "d", "f"] {
+        for to in graph.successor_names(from) {
+            if (from, to) != ("c", "a") {
+                dag.add_edge(from, to);
+            }
+        }
+    }
+    dag.intern("f");
+    let order = dag.topological_sort().unwrap();
+    assert_eq!(order.len(), 6);
+    for (from, to) in
+        (0..dag.len()).flat_map(|id| dag.successors(id).iter().map(move |to| (id, *to)))
+    {
+        let position = |id| order.iter().position(|o| *o == id).unwrap();
+        assert!(position(from) < position(to));
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use super::Graph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Ellipse,
+    Box,
+    Diamond,
+    Circle,
+    DoubleCircle,
+}
+
+impl Shape {
+    fn name(self) -> &'static str {
+        match self {
+            Shape::Ellipse => "ellipse",
+            Shape::Box => "box",
+            Shape::Diamond => "diamond",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DotNode {
+    id: String,
+    label: Option<String>,
+    shape: Option<Shape>,
+}
+
+#[derive(Debug, Clone)]
+struct DotEdge {
+    from: String,
+    to: String,
+    label: Option<String>,
+}
+
+// a graph in Graphviz's DOT language, nodes and edges are written in the order they were added
+#[derive(Debug, Clone)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    nodes: Vec<DotNode>,
+    edges: Vec<DotEdge>,
+}
+
+impl Dot {
+    pub fn digraph(name: &str) -> Self {
+        Dot {
+            name: name.to_string(),
+            directed: true,
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    pub fn graph(name: &str) -> Self {
+        Dot {
+            directed: false,
+            ..Dot::digraph(name)
+        }
+    }
+
+    pub fn add_node(&mut self, id: &str, label: Option<&str>, shape: Option<Shape>) {
+        self.nodes.push(DotNode {
+            id: id.to_string(),
+            label: label.map(str::to_string),
+            shape,
+        });
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: &str, label: Option<&str>) {
+        self.edges.push(DotEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.map(str::to_string),
+        });
+    }
+}
+
+fn quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+fn attributes(attributes: &[(&str, Option<String>)]) -> String {
+    let set = attributes
+        .iter()
+        .filter_map(|(key, value)| Some(format!("{}={}", key, value.as_ref()?)))
+        .join(", ");
+    if set.is_empty() {
+        set
+    } else {
+        format!(" [{}]", set)
+    }
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{} {} {{", kind, quote(&self.name))?;
+        for node in &self.nodes {
+            let attributes = attributes(&[
+                ("label", node.label.as_deref().map(quote)),
+                ("shape", node.shape.map(|s| s.name().to_string())),
+            ]);
+            writeln!(f, "    {}{};", quote(&node.id), attributes)?;
+        }
+        for edge in &self.edges {
+            let attributes = attributes(&[("label", edge.label.as_deref().map(quote))]);
+            writeln!(
+                f,
+                "    {} {} {}{};",
+                quote(&edge.from),
+                arrow,
+                quote(&edge.to),
+                attributes
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl Graph<'_> {
+    // every node and edge, in the order the nodes were first seen
+    pub fn to_dot(&self, name: &str, shape: impl Fn(&str) -> Shape) -> Dot {
+        let mut dot = Dot::digraph(name);
+        for name in &self.names {
+            dot.add_node(name, None, Some(shape(name)));
+        }
+        for (from, successors) in self.successors.iter().enumerate() {
+            for to in successors {
+                dot.add_edge(self.names[from], self.names[*to], None);
+            }
+        }
+        dot
+    }
+}
+
+#[test]
+fn test_dot() {
+    let mut graph = Graph::new();
+    graph.add_edges("a", ["b", "c"]);
+    graph.add_edge("c", "a");
+    let dot = graph.to_dot("test", |name| {
+        if name == "a" {
+            Shape::Box
+        } else {
+            Shape::Ellipse
+        }
+    });
+    assert_eq!(
+        dot.to_string(),
+        "digraph \"test\" {\n    \"a\" [shape=box];\n    \"b\" [shape=ellipse];\n    \"c\" [shape=ellipse];\n    \"a\" -> \"b\";\n    \"a\" -> \"c\";\n    \"c\" -> \"a\";\n}\n"
+    );
+    let mut dot = Dot::graph("quoting");
+    dot.add_node("x", Some("say \"hi\"\nC:\\"), None);
+    dot.add_edge("x", "y", Some("1"));
+    assert_eq!(
+        dot.to_string(),
+        "graph \"quoting\" {\n    \"x\" [label=\"say \\\"hi\\\"\\nC:\\\\\"];\n    \"x\" -- \"y\" [label=\"1\"];\n}\n"
+    );
+}
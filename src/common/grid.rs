@@ -1,23 +1,108 @@
 use std::{
+    collections::HashMap,
+    error::Error,
     fmt::{Debug, Display},
-    ops::Rem,
+    ops::{Index, IndexMut},
+    str::FromStr,
 };
 
 use itertools::Itertools;
 
-#[derive(Debug)]
+pub use self::bits::BitGrid;
+pub use self::direction::{Direction, Direction8};
+pub use self::fill::Connectivity;
+pub use self::point::{Point2, Vector2};
+pub use self::sparse::SparseGrid;
+
+mod bits;
+mod direction;
+pub mod fill;
+mod point;
+pub mod render;
+mod sparse;
+pub mod tiled;
+pub mod transform;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SquareGrid<T> {
     pub width: usize,
     pub height: usize,
     pub data: Vec<T>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for GridParseError {}
+
+pub type GridMarkers = HashMap<char, Vec<(usize, usize)>>;
+
 impl<T> SquareGrid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridParseError> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((y, row)) = rows.iter().find_position(|row| row.len() != width) {
+            return Err(GridParseError {
+                line: y + 1,
+                column: row.len().min(width) + 1,
+                message: format!("row has width {}, expected {}", row.len(), width),
+            });
+        }
+        Ok(SquareGrid {
+            width,
+            height: rows.len(),
+            data: rows.into_iter().flatten().collect_vec(),
+        })
+    }
+
+    pub fn parse<E: Display>(
+        input: &str,
+        parse_cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, GridParseError> {
+        Self::parse_with_markers(input, &[], parse_cell).map(|(grid, _)| grid)
+    }
+
+    pub fn parse_with_markers<E: Display>(
+        input: &str,
+        markers: &[char],
+        mut parse_cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<(Self, GridMarkers), GridParseError> {
+        let mut found_markers: GridMarkers = HashMap::new();
+        let mut rows = vec![];
+        for (y, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let mut row = vec![];
+            for (x, c) in line.chars().enumerate() {
+                if markers.contains(&c) {
+                    found_markers.entry(c).or_default().push((x, y));
+                }
+                row.push(parse_cell(c).map_err(|err| GridParseError {
+                    line: y + 1,
+                    column: x + 1,
+                    message: format!("invalid cell `{}`: {}", c, err),
+                })?);
+            }
+            rows.push(row);
+        }
+        Ok((Self::from_rows(rows)?, found_markers))
+    }
+
     pub fn contains(&self, x: usize, y: usize) -> bool {
         (0..self.width).contains(&x) && (0..self.height).contains(&y)
     }
 
-    #[allow(dead_code)]
     pub fn get_pos(&self, index: usize) -> (usize, usize) {
         (index % self.width, index / self.width)
     }
@@ -30,12 +115,6 @@ impl<T> SquareGrid<T> {
         }
     }
 
-    pub fn get_infinitely_looping(&self, x: i64, y: i64) -> Option<&T> {
-        let x = x.rem(self.width as i64) as usize;
-        let y = y.rem(self.height as i64) as usize;
-        self.get(x, y)
-    }
-
     pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
         if self.contains(x, y) {
             self.data[y * self.width + x] = value;
@@ -46,29 +125,17 @@ impl<T> SquareGrid<T> {
     }
 
     pub fn travel(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
-        if self.contains(x, y) {
-            match direction {
-                Direction::Up => {
-                    if y == 0 {
-                        None
-                    } else {
-                        Some((x, y - 1))
-                    }
-                }
-                Direction::Down => Some((x, y + 1)),
-                Direction::Left => {
-                    if x == 0 {
-                        None
-                    } else {
-                        Some((x - 1, y))
-                    }
-                }
-                Direction::Right => Some((x + 1, y)),
-            }
-            .filter(|(x2, y2)| self.contains(*x2, *y2))
-        } else {
-            None
+        self.travel_8(x, y, direction.into())
+    }
+
+    pub fn travel_8(&self, x: usize, y: usize, direction: Direction8) -> Option<(usize, usize)> {
+        if !self.contains(x, y) {
+            return None;
         }
+        (Point2::new(x as i64, y as i64) + direction.delta())
+            .try_cast::<usize>()
+            .filter(|p| self.contains_point(*p))
+            .map(<(usize, usize)>::from)
     }
 
     pub fn neighbors_8(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
@@ -91,6 +158,59 @@ impl<T> SquareGrid<T> {
             .filter(|(x2, y2)| x == *x2 || y == *y2)
             .collect_vec()
     }
+
+    pub fn contains_point(&self, p: Point2<usize>) -> bool {
+        self.contains(p.x, p.y)
+    }
+
+    pub fn get_point(&self, p: Point2<usize>) -> Option<&T> {
+        self.get(p.x, p.y)
+    }
+
+    pub fn set_point(&mut self, p: Point2<usize>, value: T) -> bool {
+        self.set(p.x, p.y, value)
+    }
+
+    pub fn travel_point(&self, p: Point2<usize>, direction: Direction) -> Option<Point2<usize>> {
+        self.travel(p.x, p.y, direction).map(Point2::from)
+    }
+
+    pub fn neighbors_4_points(&self, p: Point2<usize>) -> Vec<Point2<usize>> {
+        self.neighbors_4(p.x, p.y)
+            .into_iter()
+            .map(Point2::from)
+            .collect_vec()
+    }
+}
+
+impl<T> Index<Point2<usize>> for SquareGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<usize>) -> &Self::Output {
+        self.get_point(p)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for SquareGrid<T> {
+    fn index_mut(&mut self, p: Point2<usize>) -> &mut Self::Output {
+        assert!(self.contains_point(p), "{} is outside the grid", p);
+        &mut self.data[p.y * self.width + p.x]
+    }
+}
+
+impl<T> Index<(usize, usize)> for SquareGrid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        &self[Point2::new(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for SquareGrid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        &mut self[Point2::new(x, y)]
+    }
 }
 
 impl<T> Display for SquareGrid<T>
@@ -108,21 +228,87 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+impl<T> FromStr for SquareGrid<T>
+where
+    T: TryFrom<char>,
+    T::Error: Display,
+{
+    type Err = GridParseError;
 
-impl Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
-            Direction::Up => "^",
-            Direction::Down => "v",
-            Direction::Left => "<",
-            Direction::Right => ">",
-        })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SquareGrid::parse(s, T::try_from)
     }
 }
+
+#[test]
+fn test_parse_grid() {
+    let grid = SquareGrid::parse("123\n456\n", |c| c.to_digit(10).ok_or("not a digit")).unwrap();
+    assert_eq!(grid.width, 3);
+    assert_eq!(grid.height, 2);
+    assert_eq!(grid.get(2, 1), Some(&6));
+    assert_eq!(grid.to_string(), "123\n456\n");
+
+    let grid: SquareGrid<char> = "ab\r\ncd".parse().unwrap();
+    assert_eq!(grid.data, vec!['a', 'b', 'c', 'd']);
+}
+
+#[test]
+fn test_parse_grid_markers() {
+    let (grid, markers) = SquareGrid::parse_with_markers("S..\n.#.\n..S\n", &['S', 'E'], |c| {
+        Ok::<bool, String>(c == '#')
+    })
+    .unwrap();
+    assert_eq!(grid.get(1, 1), Some(&true));
+    assert_eq!(markers[&'S'], vec![(0, 0), (2, 2)]);
+    assert!(!markers.contains_key(&'E'));
+}
+
+#[test]
+fn test_parse_grid_errors() {
+    assert_eq!(
+        SquareGrid::parse("12\n3x\n", |c| c.to_digit(10).ok_or("not a digit")).unwrap_err(),
+        GridParseError {
+            line: 2,
+            column: 2,
+            message: "invalid cell `x`: not a digit".to_owned()
+        }
+    );
+    assert_eq!(
+        "abc\nab\nabc".parse::<SquareGrid<char>>().unwrap_err(),
+        GridParseError {
+            line: 2,
+            column: 3,
+            message: "row has width 2, expected 3".to_owned()
+        }
+    );
+    assert_eq!(
+        SquareGrid::from_rows(vec![vec![1, 2], vec![3, 4, 5]]).unwrap_err(),
+        GridParseError {
+            line: 2,
+            column: 3,
+            message: "row has width 3, expected 2".to_owned()
+        }
+    );
+}
+
+#[test]
+fn test_grid_points() {
+    let mut grid: SquareGrid<char> = "ab\ncd".parse().unwrap();
+    let p = Point2::new(1, 0);
+    assert!(grid.contains_point(p));
+    assert_eq!(grid.get_point(p), Some(&'b'));
+    assert_eq!(grid[p], 'b');
+    assert_eq!(grid[(0, 1)], 'c');
+    assert_eq!(
+        grid.travel_point(p, Direction::Down),
+        Some(Point2::new(1, 1))
+    );
+    assert_eq!(grid.travel_point(p, Direction::Right), None);
+    assert_eq!(grid.travel_8(1, 0, Direction8::DownLeft), Some((0, 1)));
+    assert_eq!(grid.travel_8(1, 0, Direction8::UpLeft), None);
+    assert_eq!(grid.neighbors_4_points(p).len(), 2);
+    grid[p] = 'x';
+    assert!(grid.set_point(Point2::new(0, 0), 'y'));
+    assert!(!grid.set_point(Point2::new(2, 0), 'z'));
+    assert_eq!(grid.to_string(), "yx\ncd\n");
+}
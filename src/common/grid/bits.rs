@@ -0,0 +1,338 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use super::{GridParseError, SquareGrid};
+
+// bit x of a row lives in word x / 64, and bits past the width are always zero
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+// ORs `word` into `dest` so that its lowest bit lands on bit `offset`, dropping bits outside `dest`
+fn or_shifted(dest: &mut [u64], word: u64, offset: i64) {
+    let index = offset.div_euclid(64);
+    let shift = offset.rem_euclid(64) as u32;
+    if (0..dest.len() as i64).contains(&index) {
+        dest[index as usize] |= word << shift;
+    }
+    if shift > 0 && (0..dest.len() as i64).contains(&(index + 1)) {
+        dest[(index + 1) as usize] |= word >> (64 - shift);
+    }
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn bit(&self, x: usize, y: usize) -> (usize, u64) {
+        (y * self.words_per_row + x / 64, 1 << (x % 64))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (i, bit) = self.bit(x, y);
+        self.contains(x, y) && self.data[i] & bit != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) -> bool {
+        if !self.contains(x, y) {
+            return false;
+        }
+        let (i, bit) = self.bit(x, y);
+        if value {
+            self.data[i] |= bit;
+        } else {
+            self.data[i] &= !bit;
+        }
+        true
+    }
+
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn last_word_mask(&self) -> u64 {
+        match self.width % 64 {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    // adds empty rows after the last one
+    pub fn extend_height(&mut self, height: usize) {
+        if height > self.height {
+            self.data.resize(self.words_per_row * height, 0);
+            self.height = height;
+        }
+    }
+
+    pub fn highest_set_row(&self) -> Option<usize> {
+        (0..self.height)
+            .rev()
+            .find(|y| self.row(*y).iter().any(|word| *word != 0))
+    }
+
+    pub fn count(&self) -> usize {
+        self.data
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // moves every cell `dx` columns to the right (or left if negative), dropping what falls off
+    pub fn shift_rows(&mut self, dx: i64) {
+        let last_word_mask = self.last_word_mask();
+        for y in 0..self.height {
+            let mut shifted = vec![0; self.words_per_row];
+            for (i, word) in self.row(y).iter().enumerate() {
+                or_shifted(&mut shifted, *word, i as i64 * 64 + dx);
+            }
+            if let Some(last) = shifted.last_mut() {
+                *last &= last_word_mask;
+            }
+            self.row_mut(y).copy_from_slice(&shifted);
+        }
+    }
+
+    // the row of `mask` shifted to start at column x, clipped to the width of this grid
+    fn mask_row_at(&self, mask: &BitGrid, mask_y: usize, x: i64) -> Vec<u64> {
+        let mut shifted = vec![0; self.words_per_row];
+        for (i, word) in mask.row(mask_y).iter().enumerate() {
+            or_shifted(&mut shifted, *word, i as i64 * 64 + x);
+        }
+        if let Some(last) = shifted.last_mut() {
+            *last &= self.last_word_mask();
+        }
+        shifted
+    }
+
+    // true if every set cell of `mask`, with its origin at (x, y), lands on an empty cell
+    pub fn can_place(&self, mask: &BitGrid, x: i64, y: i64) -> bool {
+        (0..mask.height).all(|mask_y| {
+            if mask.row(mask_y).iter().all(|word| *word == 0) {
+                return true;
+            }
+            let grid_y = y + mask_y as i64;
+            if !(0..self.height as i64).contains(&grid_y) {
+                return false;
+            }
+            let shifted = self.mask_row_at(mask, mask_y, x);
+            let count = |words: &[u64]| words.iter().map(|w| w.count_ones()).sum::<u32>();
+            count(&shifted) == count(mask.row(mask_y))
+                && shifted
+                    .iter()
+                    .zip(self.row(grid_y as usize))
+                    .all(|(a, b)| a & b == 0)
+        })
+    }
+
+    // sets the cells of `mask` with its origin at (x, y), parts outside the grid are ignored
+    pub fn place(&mut self, mask: &BitGrid, x: i64, y: i64) {
+        for mask_y in 0..mask.height {
+            let grid_y = y + mask_y as i64;
+            if !(0..self.height as i64).contains(&grid_y) {
+                continue;
+            }
+            let shifted = self.mask_row_at(mask, mask_y, x);
+            for (a, b) in self.row_mut(grid_y as usize).iter_mut().zip(shifted) {
+                *a |= b;
+            }
+        }
+    }
+
+    fn combine(&self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) -> BitGrid {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids must have the same size"
+        );
+        BitGrid {
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| f(*a, *b))
+                .collect_vec(),
+            ..self.clone()
+        }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn flip_v(&self) -> BitGrid {
+        BitGrid {
+            data: (0..self.height)
+                .rev()
+                .flat_map(|y| self.row(y).iter().copied())
+                .collect_vec(),
+            ..self.clone()
+        }
+    }
+
+    // `rows` rows starting at y, rows past the end of the grid count as empty
+    pub fn row_window(&self, y: usize, rows: usize) -> Vec<u64> {
+        (y..y + rows)
+            .flat_map(|y| {
+                if y < self.height {
+                    self.row(y).to_vec()
+                } else {
+                    vec![0; self.words_per_row]
+                }
+            })
+            .collect_vec()
+    }
+
+    pub fn hash_window(&self, y: usize, rows: usize) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.row_window(y, rows).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl From<&SquareGrid<bool>> for BitGrid {
+    fn from(grid: &SquareGrid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width, grid.height);
+        for ((x, y), value) in grid.iter_with_pos() {
+            bits.set(x, y, *value);
+        }
+        bits
+    }
+}
+
+impl FromStr for BitGrid {
+    type Err = GridParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = SquareGrid::parse(s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected `#` or `.`"),
+        })?;
+        Ok(BitGrid::from(&grid))
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_bit_grid_get_set() {
+    let mut grid = BitGrid::new(100, 3);
+    assert!(grid.set(0, 0, true));
+    assert!(grid.set(64, 1, true));
+    assert!(grid.set(99, 2, true));
+    assert!(!grid.set(100, 0, true));
+    assert!(grid.get(64, 1));
+    assert!(!grid.get(63, 1));
+    assert_eq!(grid.count(), 3);
+    assert_eq!(grid.highest_set_row(), Some(2));
+    grid.set(99, 2, false);
+    assert_eq!(grid.highest_set_row(), Some(1));
+    grid.extend_height(5);
+    assert_eq!((grid.width(), grid.height()), (100, 5));
+    assert_eq!(grid.count(), 2);
+}
+
+#[test]
+fn test_bit_grid_shift_rows() {
+    let mut grid: BitGrid = "#..#\n.##.".parse().unwrap();
+    grid.shift_rows(1);
+    assert_eq!(grid.to_string(), ".#..\n..##\n");
+    grid.shift_rows(-2);
+    assert_eq!(grid.to_string(), "....\n##..\n");
+
+    let mut wide = BitGrid::new(130, 1);
+    wide.set(62, 0, true);
+    wide.set(127, 0, true);
+    wide.shift_rows(2);
+    assert!(wide.get(64, 0));
+    assert!(wide.get(129, 0));
+    wide.shift_rows(1);
+    assert_eq!(wide.count(), 1);
+    assert!(wide.get(65, 0));
+}
+
+#[test]
+fn test_bit_grid_place() {
+    let mut grid = BitGrid::new(7, 4);
+    let plus: BitGrid = ".#.\n###\n.#.".parse().unwrap();
+    assert!(grid.can_place(&plus, 0, 0));
+    assert!(grid.can_place(&plus, 4, 1));
+    assert!(!grid.can_place(&plus, 5, 0));
+    assert!(!grid.can_place(&plus, -1, 0));
+    assert!(!grid.can_place(&plus, 0, 2));
+    grid.place(&plus, 2, 0);
+    assert_eq!(grid.to_string(), "...#...\n..###..\n...#...\n.......\n");
+    // the empty corners of the mask may overlap set cells
+    assert!(grid.can_place(&plus, 0, 1));
+    assert!(!grid.can_place(&plus, 1, 1));
+    let mut wide = BitGrid::new(70, 1);
+    wide.place(&"##".parse().unwrap(), 63, 0);
+    assert!(wide.get(63, 0) && wide.get(64, 0));
+}
+
+#[test]
+fn test_bit_grid_combine() {
+    let a: BitGrid = "##..\n#...".parse().unwrap();
+    let b: BitGrid = ".##.\n#..#".parse().unwrap();
+    assert_eq!(a.union(&b).to_string(), "###.\n#..#\n");
+    assert_eq!(a.intersection(&b).to_string(), ".#..\n#...\n");
+    assert_eq!(a.union(&b).count(), 5);
+    assert_eq!(a.flip_v().to_string(), "#...\n##..\n");
+}
+
+#[test]
+fn test_bit_grid_window() {
+    let grid: BitGrid = "#..\n.#.\n..#".parse().unwrap();
+    assert_eq!(grid.row_window(1, 3), vec![0b010, 0b100, 0]);
+    assert_eq!(grid.hash_window(1, 2), grid.clone().hash_window(1, 2));
+    assert_ne!(grid.hash_window(0, 2), grid.hash_window(1, 2));
+    assert!("#x".parse::<BitGrid>().is_err());
+}
@@ -0,0 +1,290 @@
+use std::{fmt::Display, str::FromStr};
+
+use num::Signed;
+
+use super::Vector2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    // clockwise, starting from Up
+    pub fn all() -> [Direction; 4] {
+        [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().opposite()
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    // y grows downwards, so Up is (0, -1)
+    pub fn delta<T: Signed>(self) -> Vector2<T> {
+        match self {
+            Direction::Up => Vector2::new(T::zero(), -T::one()),
+            Direction::Down => Vector2::new(T::zero(), T::one()),
+            Direction::Left => Vector2::new(-T::one(), T::zero()),
+            Direction::Right => Vector2::new(T::one(), T::zero()),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' => Ok(Direction::Up),
+            'D' | 'S' | 'v' => Ok(Direction::Down),
+            'L' | 'W' | '<' => Ok(Direction::Left),
+            'R' | 'E' | '>' => Ok(Direction::Right),
+            _ => Err("not a direction"),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.chars().collect::<Vec<_>>()[..] {
+            [c] => Direction::try_from(c),
+            _ => Err("not a direction"),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Direction::Up => "^",
+            Direction::Down => "v",
+            Direction::Left => "<",
+            Direction::Right => ">",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    // clockwise, starting from Up
+    pub fn all() -> [Direction8; 8] {
+        [
+            Direction8::Up,
+            Direction8::UpRight,
+            Direction8::Right,
+            Direction8::DownRight,
+            Direction8::Down,
+            Direction8::DownLeft,
+            Direction8::Left,
+            Direction8::UpLeft,
+        ]
+    }
+
+    fn index(self) -> usize {
+        Direction8::all()
+            .into_iter()
+            .position(|d| d == self)
+            .unwrap()
+    }
+
+    fn rotate(self, eighths: usize) -> Direction8 {
+        Direction8::all()[(self.index() + eighths) % 8]
+    }
+
+    // turns by 45 degrees
+    pub fn turn_left(self) -> Direction8 {
+        self.rotate(7)
+    }
+
+    pub fn turn_right(self) -> Direction8 {
+        self.rotate(1)
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn delta<T: Signed>(self) -> Vector2<T> {
+        match self {
+            Direction8::Up => Direction::Up.delta(),
+            Direction8::Right => Direction::Right.delta(),
+            Direction8::Down => Direction::Down.delta(),
+            Direction8::Left => Direction::Left.delta(),
+            Direction8::UpRight => Direction::Up.delta() + Direction::Right.delta(),
+            Direction8::DownRight => Direction::Down.delta() + Direction::Right.delta(),
+            Direction8::DownLeft => Direction::Down.delta() + Direction::Left.delta(),
+            Direction8::UpLeft => Direction::Up.delta() + Direction::Left.delta(),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = &'static str;
+
+    fn try_from(direction: Direction8) -> Result<Self, Self::Error> {
+        match direction {
+            Direction8::Up => Ok(Direction::Up),
+            Direction8::Down => Ok(Direction::Down),
+            Direction8::Left => Ok(Direction::Left),
+            Direction8::Right => Ok(Direction::Right),
+            _ => Err("diagonal direction"),
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = &'static str;
+
+    // diagonals are written as two directions, e.g. `NE`, `UR` or `^>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let directions = s
+            .chars()
+            .map(Direction::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let vertical = directions.iter().find(|d| d.is_vertical());
+        let horizontal = directions.iter().find(|d| d.is_horizontal());
+        match (directions.len(), vertical, horizontal) {
+            (1, _, _) => Ok(directions[0].into()),
+            (2, Some(vertical), Some(horizontal)) => Direction8::all()
+                .into_iter()
+                .find(|d| d.delta::<i32>() == vertical.delta() + horizontal.delta())
+                .ok_or("not a direction"),
+            _ => Err("not a direction"),
+        }
+    }
+}
+
+impl Display for Direction8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Direction8::Up => "N",
+            Direction8::UpRight => "NE",
+            Direction8::Right => "E",
+            Direction8::DownRight => "SE",
+            Direction8::Down => "S",
+            Direction8::DownLeft => "SW",
+            Direction8::Left => "W",
+            Direction8::UpLeft => "NW",
+        })
+    }
+}
+
+#[test]
+fn test_direction_turns() {
+    for direction in Direction::all() {
+        assert_eq!(direction.turn_left().turn_right(), direction);
+        assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+        assert_eq!(direction.opposite().opposite(), direction);
+        assert_eq!(
+            direction.turn_right().delta::<i32>(),
+            direction.delta::<i32>().rotate_cw()
+        );
+        assert_ne!(direction.is_vertical(), direction.is_horizontal());
+    }
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+}
+
+#[test]
+fn test_direction_parse() {
+    for s in ["U", "N", "^"] {
+        assert_eq!(s.parse(), Ok(Direction::Up));
+    }
+    for s in ["R", "E", ">"] {
+        assert_eq!(s.parse(), Ok(Direction::Right));
+    }
+    assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+    assert_eq!(Direction::try_from('W'), Ok(Direction::Left));
+    assert!("X".parse::<Direction>().is_err());
+    assert!("UR".parse::<Direction>().is_err());
+    for direction in Direction::all() {
+        assert_eq!(direction.to_string().parse(), Ok(direction));
+    }
+}
+
+#[test]
+fn test_direction8() {
+    for direction in Direction8::all() {
+        assert_eq!(direction.turn_left().turn_right(), direction);
+        assert_eq!((0..8).fold(direction, |d, _| d.turn_right()), direction);
+        assert_eq!(
+            direction.opposite().delta::<i32>(),
+            -direction.delta::<i32>()
+        );
+        assert_eq!(direction.to_string().parse(), Ok(direction));
+    }
+    assert_eq!(
+        Direction8::Up.turn_right().turn_right(),
+        Direction8::from(Direction::Up.turn_right())
+    );
+    assert_eq!("UR".parse(), Ok(Direction8::UpRight));
+    assert_eq!("v<".parse(), Ok(Direction8::DownLeft));
+    assert_eq!("NW".parse(), Ok(Direction8::UpLeft));
+    assert!("NS".parse::<Direction8>().is_err());
+    assert!(Direction8::DownRight.is_diagonal());
+    assert!(!Direction8::Down.is_diagonal());
+    assert_eq!(Direction8::DownRight.delta::<i64>(), Vector2::new(1, 1));
+    assert!(Direction::try_from(Direction8::UpLeft).is_err());
+}
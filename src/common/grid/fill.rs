@@ -0,0 +1,258 @@
+use std::{collections::HashSet, hash::Hash};
+
+use itertools::Itertools;
+
+use super::{BitGrid, Direction, Direction8, Point2, SquareGrid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+// everything reachable from `starts`, including the starts themselves
+pub fn flood_fill<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut queue = starts.into_iter().collect_vec();
+    let mut visited: HashSet<N> = queue.iter().cloned().collect();
+    while let Some(node) = queue.pop() {
+        for neighbor in neighbors(&node) {
+            if visited.insert(neighbor.clone()) {
+                queue.push(neighbor);
+            }
+        }
+    }
+    visited
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    pub labels: SquareGrid<Option<usize>>,
+    pub sizes: Vec<usize>,
+}
+
+impl Components {
+    fn same_region(&self, label: usize, p: Point2<usize>, direction: Direction8) -> bool {
+        let (x, y) = p.into();
+        self.labels
+            .travel_8(x, y, direction)
+            .is_some_and(|(x, y)| self.labels[(x, y)] == Some(label))
+    }
+
+    // cell sides bordering another region or the edge of the grid
+    pub fn perimeters(&self) -> Vec<usize> {
+        let mut perimeters = vec![0; self.sizes.len()];
+        for ((x, y), label) in self.labels.iter_with_pos() {
+            if let Some(label) = *label {
+                perimeters[label] += Direction::all()
+                    .into_iter()
+                    .filter(|d| !self.same_region(label, Point2::new(x, y), (*d).into()))
+                    .count();
+            }
+        }
+        perimeters
+    }
+
+    // straight edges of each region, which is the same as its number of corners
+    pub fn sides(&self) -> Vec<usize> {
+        let mut sides = vec![0; self.sizes.len()];
+        for ((x, y), label) in self.labels.iter_with_pos() {
+            if let Some(label) = *label {
+                let p = Point2::new(x, y);
+                for d in Direction::all() {
+                    let a = self.same_region(label, p, d.into());
+                    let b = self.same_region(label, p, d.turn_right().into());
+                    let diagonal = Direction8::from(d).turn_right();
+                    let convex = !a && !b;
+                    let concave = a && b && !self.same_region(label, p, diagonal);
+                    if convex || concave {
+                        sides[label] += 1;
+                    }
+                }
+            }
+        }
+        sides
+    }
+}
+
+impl<T> SquareGrid<T> {
+    pub fn neighbors(&self, x: usize, y: usize, connectivity: Connectivity) -> Vec<(usize, usize)> {
+        match connectivity {
+            Connectivity::Four => self.neighbors_4(x, y),
+            Connectivity::Eight => self.neighbors_8(x, y),
+        }
+    }
+
+    fn fill_from(
+        &self,
+        starts: Vec<(usize, usize)>,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> BitGrid {
+        let mut filled = BitGrid::new(self.width, self.height);
+        let mut queue = starts
+            .into_iter()
+            .filter(|(x, y)| self.get(*x, *y).is_some_and(&passable))
+            .collect_vec();
+        for (x, y) in &queue {
+            filled.set(*x, *y, true);
+        }
+        while let Some((x, y)) = queue.pop() {
+            for (nx, ny) in self.neighbors(x, y, connectivity) {
+                if !filled.get(nx, ny) && passable(&self[(nx, ny)]) {
+                    filled.set(nx, ny, true);
+                    queue.push((nx, ny));
+                }
+            }
+        }
+        filled
+    }
+
+    // the passable cells connected to `start`, empty if `start` itself isn't passable
+    pub fn flood_fill(
+        &self,
+        start: (usize, usize),
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> BitGrid {
+        self.fill_from(vec![start], connectivity, passable)
+    }
+
+    pub fn reachable_from_border(
+        &self,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> BitGrid {
+        let border = (0..self.width)
+            .flat_map(|x| [(x, 0), (x, self.height.saturating_sub(1))])
+            .chain((0..self.height).flat_map(|y| [(0, y), (self.width.saturating_sub(1), y)]))
+            .collect_vec();
+        self.fill_from(border, connectivity, passable)
+    }
+
+    pub fn label_components(
+        &self,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> Components {
+        self.label_connected(connectivity, &passable, |_, next| passable(next))
+    }
+
+    // every cell gets a label, neighbours share one when `same_region` holds for them,
+    // like `|a, b| a == b` for regions of equal cells
+    pub fn label_regions(
+        &self,
+        connectivity: Connectivity,
+        same_region: impl Fn(&T, &T) -> bool,
+    ) -> Components {
+        self.label_connected(connectivity, |_| true, same_region)
+    }
+
+    fn label_connected(
+        &self,
+        connectivity: Connectivity,
+        included: impl Fn(&T) -> bool,
+        joined: impl Fn(&T, &T) -> bool,
+    ) -> Components {
+        let mut labels = self.map(|_| None);
+        let mut sizes = vec![];
+        for (x, y) in (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y))) {
+            if labels[(x, y)].is_some() || !included(&self[(x, y)]) {
+                continue;
+            }
+            let label = sizes.len();
+            let mut size = 0;
+            let mut queue = vec![(x, y)];
+            labels[(x, y)] = Some(label);
+            while let Some((x, y)) = queue.pop() {
+                size += 1;
+                for (nx, ny) in self.neighbors(x, y, connectivity) {
+                    if labels[(nx, ny)].is_none() && joined(&self[(x, y)], &self[(nx, ny)]) {
+                        labels[(nx, ny)] = Some(label);
+                        queue.push((nx, ny));
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+        Components { labels, sizes }
+    }
+}
+
+#[cfg(test)]
+const FIXTURE: &str = "\
+#####
+#..##
+#.#.#
+##..#
+#...#";
+
+#[test]
+fn test_flood_fill() {
+    let grid: SquareGrid<char> = FIXTURE.parse().unwrap();
+    let open = |c: &char| *c == '.';
+    assert_eq!(grid.flood_fill((1, 1), Connectivity::Four, open).count(), 3);
+    assert_eq!(
+        grid.flood_fill((1, 1), Connectivity::Eight, open).count(),
+        9
+    );
+    assert_eq!(
+        grid.flood_fill((0, 0), Connectivity::Eight, open).count(),
+        0
+    );
+    assert_eq!(
+        grid.reachable_from_border(Connectivity::Four, open).count(),
+        6
+    );
+    assert_eq!(
+        grid.reachable_from_border(Connectivity::Four, |c| *c == '#')
+            .count(),
+        15
+    );
+}
+
+#[test]
+fn test_label_components() {
+    let grid: SquareGrid<char> = FIXTURE.parse().unwrap();
+    let components = grid.label_components(Connectivity::Four, |c| *c == '.');
+    assert_eq!(components.sizes, vec![3, 6]);
+    assert_eq!(components.labels[(1, 2)], Some(0));
+    assert_eq!(components.labels[(3, 2)], Some(1));
+    assert_eq!(components.labels[(2, 2)], None);
+    let components = grid.label_components(Connectivity::Eight, |c| *c == '.');
+    assert_eq!(components.sizes, vec![9]);
+}
+
+#[test]
+fn test_region_perimeters_and_sides() {
+    let grid: SquareGrid<char> = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+    let components = grid.label_regions(Connectivity::Four, |a, b| a == b);
+    assert_eq!(components.sizes, vec![4, 4, 4, 1, 3]);
+    assert_eq!(components.labels[(3, 1)], Some(3));
+    assert_eq!(components.perimeters(), vec![10, 8, 10, 4, 8]);
+    assert_eq!(components.sides(), vec![4, 4, 8, 4, 4]);
+    // the same plant in two places is two regions, unless they touch diagonally
+    let grid: SquareGrid<char> = "AB\nBA".parse().unwrap();
+    let components = grid.label_regions(Connectivity::Four, |a, b| a == b);
+    assert_eq!(components.sizes, vec![1, 1, 1, 1]);
+    assert_eq!(components.sides(), vec![4, 4, 4, 4]);
+    let components = grid.label_regions(Connectivity::Eight, |a, b| a == b);
+    assert_eq!(components.sizes, vec![2, 2]);
+}
+
+#[test]
+fn test_generic_flood_fill() {
+    let reachable = flood_fill([0], |n: &i32| {
+        [n + 3, n - 2].into_iter().filter(|n| n.abs() < 5)
+    });
+    assert_eq!(
+        reachable.into_iter().sorted().collect_vec(),
+        (-4..5).collect_vec()
+    );
+}
@@ -0,0 +1,207 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use num::{Num, Signed};
+
+use super::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+pub type Vector2<T> = Point2<T>;
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point2<U>> {
+        Some(Point2::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+fn abs_diff<T: Num + PartialOrd>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Num + PartialOrd + Copy> Point2<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+// y grows downwards, so clockwise takes Up to Right
+impl<T: Signed + Copy> Point2<T> {
+    pub fn rotate_cw(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    pub fn rotate_cw_steps(self, steps: i32) -> Self {
+        (0..steps.rem_euclid(4)).fold(self, |p, _| p.rotate_cw())
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Signed> From<Direction> for Point2<T> {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+impl<T: Signed + Copy> TryFrom<Point2<T>> for Direction {
+    type Error = &'static str;
+
+    fn try_from(p: Point2<T>) -> Result<Self, Self::Error> {
+        Direction::all()
+            .into_iter()
+            .find(|direction| direction.delta() == p)
+            .ok_or("not a unit vector along an axis")
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[test]
+fn test_point_arithmetic() {
+    let a = Point2::new(3, -2);
+    let b = Point2::new(-1, 5);
+    assert_eq!(a + b, Point2::new(2, 3));
+    assert_eq!(a - b, Point2::new(4, -7));
+    assert_eq!(a * 3, Point2::new(9, -6));
+    assert_eq!(-a, Point2::new(-3, 2));
+    let mut c = a;
+    c += b;
+    c -= Point2::new(1, 1);
+    assert_eq!(c, Point2::new(1, 2));
+}
+
+#[test]
+fn test_point_distances() {
+    let a = Point2::new(3, -2);
+    let b = Point2::new(-1, 5);
+    assert_eq!(a.manhattan_distance(&b), 11);
+    assert_eq!(a.chebyshev_distance(&b), 7);
+    let c = Point2::new(2usize, 10);
+    let d = Point2::new(7usize, 4);
+    assert_eq!(c.manhattan_distance(&d), 11);
+    assert_eq!(d.manhattan_distance(&c), 11);
+    assert_eq!(c.chebyshev_distance(&d), 6);
+}
+
+#[test]
+fn test_point_rotation() {
+    let up: Point2<i32> = Direction::Up.into();
+    assert_eq!(up.rotate_cw(), Point2::from(Direction::Right));
+    assert_eq!(up.rotate_ccw(), Point2::from(Direction::Left));
+    assert_eq!(up.rotate_cw_steps(2), Point2::from(Direction::Down));
+    assert_eq!(up.rotate_cw_steps(-1), Point2::from(Direction::Left));
+    let p = Point2::new(4, 7);
+    assert_eq!(p.rotate_cw_steps(4), p);
+    assert_eq!(p.rotate_cw().rotate_ccw(), p);
+}
+
+#[test]
+fn test_point_conversions() {
+    assert_eq!(
+        Direction::try_from(Point2::new(1i64, 0)),
+        Ok(Direction::Right)
+    );
+    assert!(Direction::try_from(Point2::new(1i64, 1)).is_err());
+    assert_eq!(
+        Point2::new(3i64, 4).try_cast::<usize>(),
+        Some(Point2::new(3usize, 4))
+    );
+    assert_eq!(Point2::new(-3i64, 4).try_cast::<usize>(), None);
+    assert_eq!(
+        Point2::new(3usize, 4).try_cast::<i32>(),
+        Some(Point2::new(3, 4))
+    );
+    assert_eq!(<(i32, i32)>::from(Point2::new(1, 2)), (1, 2));
+}
@@ -0,0 +1,272 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{self, IsTerminal},
+};
+
+use itertools::Itertools;
+
+use super::{transform::Rect, SquareGrid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        30 + self as u8
+    }
+}
+
+// cells drawn over the grid, each showing its own glyph or else the layer's,
+// a layer without either only changes the colour
+#[derive(Debug, Clone, Default)]
+pub struct Layer {
+    glyph: Option<char>,
+    color: Option<Color>,
+    cells: HashMap<(usize, usize), Option<char>>,
+}
+
+impl Layer {
+    pub fn new(glyph: Option<char>, color: Option<Color>) -> Self {
+        Layer {
+            glyph,
+            color,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn with_cells(mut self, cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.cells.extend(cells.into_iter().map(|p| (p, None)));
+        self
+    }
+
+    pub fn with_glyphs(mut self, cells: impl IntoIterator<Item = ((usize, usize), char)>) -> Self {
+        self.cells
+            .extend(cells.into_iter().map(|(p, glyph)| (p, Some(glyph))));
+        self
+    }
+
+    fn glyph_at(&self, p: (usize, usize)) -> Option<Option<char>> {
+        self.cells.get(&p).map(|glyph| glyph.or(self.glyph))
+    }
+}
+
+pub struct Renderer<'a> {
+    width: usize,
+    height: usize,
+    glyph: Box<dyn Fn(usize, usize) -> char + 'a>,
+    layers: Vec<Layer>,
+    viewport: Option<Rect>,
+    colors: Option<bool>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(width: usize, height: usize, glyph: impl Fn(usize, usize) -> char + 'a) -> Self {
+        Renderer {
+            width,
+            height,
+            glyph: Box::new(glyph),
+            layers: vec![],
+            viewport: None,
+            colors: None,
+        }
+    }
+
+    // later layers are drawn on top of earlier ones
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    pub fn path(
+        self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        glyph: Option<char>,
+        color: Color,
+    ) -> Self {
+        self.layer(Layer::new(glyph, Some(color)).with_cells(cells))
+    }
+
+    pub fn highlight(self, cells: impl IntoIterator<Item = (usize, usize)>, color: Color) -> Self {
+        self.layer(Layer::new(None, Some(color)).with_cells(cells))
+    }
+
+    pub fn cursor(self, p: (usize, usize), glyph: char, color: Color) -> Self {
+        self.layer(Layer::new(Some(glyph), Some(color)).with_cells([p]))
+    }
+
+    // only draws the part of the grid inside `viewport`
+    pub fn viewport(mut self, viewport: Rect) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    // a viewport of at most `width` by `height` cells around `center`, kept inside the grid
+    pub fn viewport_around(self, center: (usize, usize), width: usize, height: usize) -> Self {
+        let start = |c: usize, size: usize, max: usize| {
+            c.saturating_sub(size / 2).min(max.saturating_sub(size))
+        };
+        let viewport = Rect::new(
+            start(center.0, width, self.width),
+            start(center.1, height, self.height),
+            width,
+            height,
+        );
+        self.viewport(viewport)
+    }
+
+    // `render` and `Display` default to plain text, `print` to colours only on a terminal
+    pub fn colors(mut self, enabled: bool) -> Self {
+        self.colors = Some(enabled);
+        self
+    }
+
+    // glyph and colour each come from the topmost layer that sets them
+    fn cell(&self, x: usize, y: usize) -> (char, Option<Color>) {
+        let glyph = self
+            .layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.glyph_at((x, y)).flatten())
+            .unwrap_or_else(|| (self.glyph)(x, y));
+        let color = self.layers.iter().rev().find_map(|layer| {
+            layer
+                .cells
+                .contains_key(&(x, y))
+                .then_some(layer.color)
+                .flatten()
+        });
+        (glyph, color)
+    }
+
+    fn render_with(&self, colors: bool) -> String {
+        let viewport = self
+            .viewport
+            .unwrap_or(Rect::new(0, 0, self.width, self.height));
+        let xs = viewport.x.min(self.width)..(viewport.x + viewport.width).min(self.width);
+        let ys = viewport.y.min(self.height)..(viewport.y + viewport.height).min(self.height);
+        ys.map(|y| {
+            let mut line = String::new();
+            let mut current = None;
+            for x in xs.clone() {
+                let (glyph, color) = self.cell(x, y);
+                if colors && color != current {
+                    match color {
+                        Some(color) => line += &format!("\x1b[{}m", color.ansi_code()),
+                        None => line += "\x1b[0m",
+                    }
+                    current = color;
+                }
+                line.push(glyph);
+            }
+            if current.is_some() {
+                line += "\x1b[0m";
+            }
+            line
+        })
+        .join("\n")
+    }
+
+    pub fn render(&self) -> String {
+        self.render_with(self.colors.unwrap_or(false))
+    }
+
+    pub fn print(&self) {
+        let colors = self.colors.unwrap_or_else(|| io::stdout().is_terminal());
+        println!("{}", self.render_with(colors));
+    }
+}
+
+impl Display for Renderer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+impl<T> SquareGrid<T> {
+    pub fn renderer<'a>(&'a self, glyph: impl Fn(&T) -> char + 'a) -> Renderer<'a> {
+        Renderer::new(self.width, self.height, move |x, y| glyph(&self[(x, y)]))
+    }
+}
+
+#[test]
+fn test_render_layers() {
+    let grid: SquareGrid<char> = "...\n.#.\n...".parse().unwrap();
+    let renderer = grid
+        .renderer(|c| *c)
+        .path([(0, 0), (1, 0), (2, 0)], Some('>'), Color::Blue)
+        .highlight([(1, 1), (2, 0)], Color::Red)
+        .cursor((0, 2), '@', Color::Yellow);
+    assert_eq!(renderer.to_string(), ">>>\n.#.\n@..");
+    assert_eq!(
+        renderer.colors(true).render(),
+        "\x1b[34m>>\x1b[31m>\x1b[0m\n.\x1b[31m#\x1b[0m.\n\x1b[33m@\x1b[0m.."
+    );
+    let glyphs = Layer::new(Some('x'), None)
+        .with_glyphs([((2, 2), '2')])
+        .with_cells([(1, 2)]);
+    assert_eq!(
+        grid.renderer(|c| *c).layer(glyphs).render(),
+        "...\n.#.\n.x2"
+    );
+}
+
+#[test]
+fn test_render_palette() {
+    let colors = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+    // the standard ANSI foreground colours, in order
+    assert_eq!(
+        colors.map(Color::ansi_code),
+        [30, 31, 32, 33, 34, 35, 36, 37]
+    );
+    let grid: SquareGrid<char> = "ab".parse().unwrap();
+    let renderer = grid
+        .renderer(|c| *c)
+        .highlight([(0, 0)], Color::Cyan)
+        .highlight([(1, 0)], Color::Magenta)
+        .colors(true);
+    assert_eq!(renderer.render(), "\x1b[36ma\x1b[35mb\x1b[0m");
+}
+
+#[test]
+fn test_render_viewport() {
+    let grid = SquareGrid::from_fn(10, 6, |x, y| {
+        char::from_digit(((x + y) % 10) as u32, 10).unwrap()
+    });
+    let renderer = grid.renderer(|c| *c);
+    assert_eq!(
+        grid.renderer(|c| *c)
+            .viewport(Rect::new(8, 4, 5, 5))
+            .render(),
+        "23\n34"
+    );
+    assert_eq!(renderer.viewport_around((0, 0), 3, 2).render(), "012\n123");
+    let renderer = grid.renderer(|c| *c).viewport_around((9, 5), 3, 2);
+    assert_eq!(renderer.render(), "123\n234");
+    assert_eq!(
+        grid.renderer(|c| *c)
+            .viewport_around((5, 3), 20, 20)
+            .render()
+            .lines()
+            .count(),
+        6
+    );
+}
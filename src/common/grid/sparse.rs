@@ -0,0 +1,258 @@
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+
+use super::{Direction, Direction8, Point2, SquareGrid};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2<i64>, T>,
+    bounds: Option<(Point2<i64>, Point2<i64>)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Point2<i64>) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point2<i64>) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point2<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn insert(&mut self, p: Point2<i64>, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+            None => (p, p),
+        });
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point2<i64>) -> Option<T> {
+        let removed = self.cells.remove(&p);
+        // only a cell on the edge of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds {
+            let on_edge = p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y;
+            if removed.is_some() && on_edge {
+                self.bounds = Self::compute_bounds(self.cells.keys());
+            }
+        }
+        removed
+    }
+
+    fn compute_bounds<'a>(
+        points: impl Iterator<Item = &'a Point2<i64>>,
+    ) -> Option<(Point2<i64>, Point2<i64>)> {
+        points.fold(None, |bounds, p| {
+            Some(match bounds {
+                Some((min, max)) => (
+                    Point2::new(p.x.min(min.x), p.y.min(min.y)),
+                    Point2::new(p.x.max(max.x), p.y.max(max.y)),
+                ),
+                None => (*p, *p),
+            })
+        })
+    }
+
+    // inclusive top-left and bottom-right corners of the occupied cells
+    pub fn bounds(&self) -> Option<(Point2<i64>, Point2<i64>)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<i64>, &T)> + '_ {
+        self.cells.iter().map(|(p, value)| (*p, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point2<i64>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn neighbors_4(&self, p: Point2<i64>) -> Vec<Point2<i64>> {
+        Direction::all()
+            .into_iter()
+            .map(|direction| p + direction.delta())
+            .collect_vec()
+    }
+
+    pub fn neighbors_8(&self, p: Point2<i64>) -> Vec<Point2<i64>> {
+        Direction8::all()
+            .into_iter()
+            .map(|direction| p + direction.delta())
+            .collect_vec()
+    }
+
+    pub fn occupied_neighbors_8(&self, p: Point2<i64>) -> Vec<(Point2<i64>, &T)> {
+        self.neighbors_8(p)
+            .into_iter()
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+            .collect_vec()
+    }
+
+    // the dense grid starts at the top-left corner of the bounds, which is returned alongside it
+    pub fn to_dense(&self, empty: T) -> (SquareGrid<T>, Point2<i64>)
+    where
+        T: Clone,
+    {
+        let origin = self.bounds.map_or(Point2::default(), |(min, _)| min);
+        let grid = SquareGrid::from_fn(self.width(), self.height(), |x, y| {
+            self.get(origin + Point2::new(x as i64, y as i64))
+                .unwrap_or(&empty)
+                .clone()
+        });
+        (grid, origin)
+    }
+
+    pub fn from_dense(grid: SquareGrid<T>, mut is_empty: impl FnMut(&T) -> bool) -> Self {
+        let width = grid.width;
+        grid.data
+            .into_iter()
+            .enumerate()
+            .filter(|(_, value)| !is_empty(value))
+            .map(|(i, value)| (Point2::new((i % width) as i64, (i / width) as i64), value))
+            .collect()
+    }
+}
+
+impl<T: Display> SparseGrid<T> {
+    pub fn render_region(&self, min: Point2<i64>, max: Point2<i64>, empty: char) -> String {
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        self.get(Point2::new(x, y))
+                            .map_or(empty.to_string(), |value| value.to_string())
+                    })
+                    .join("")
+            })
+            .join("\n")
+    }
+
+    pub fn render(&self, empty: char) -> String {
+        self.bounds.map_or(String::new(), |(min, max)| {
+            self.render_region(min, max, empty)
+        })
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render('.'))
+    }
+}
+
+impl<T> FromIterator<(Point2<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2<i64>, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point2<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point2<i64>, T)>>(&mut self, iter: I) {
+        for (p, value) in iter {
+            self.insert(p, value);
+        }
+    }
+}
+
+#[test]
+fn test_sparse_grid_bounds() {
+    let mut grid = SparseGrid::new();
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.width(), 0);
+    grid.insert(Point2::new(2, 3), 'a');
+    grid.insert(Point2::new(-1, 5), 'b');
+    grid.insert(Point2::new(0, 4), 'c');
+    assert_eq!(grid.bounds(), Some((Point2::new(-1, 3), Point2::new(2, 5))));
+    assert_eq!((grid.width(), grid.height()), (4, 3));
+    assert_eq!(
+        grid.points().sorted_by_key(|p| (p.y, p.x)).collect_vec(),
+        vec![Point2::new(2, 3), Point2::new(0, 4), Point2::new(-1, 5)]
+    );
+    // changing a value in place doesn't move the bounds
+    *grid.get_mut(Point2::new(0, 4)).unwrap() = 'd';
+    assert_eq!(grid.get(Point2::new(0, 4)), Some(&'d'));
+    assert_eq!(grid.get_mut(Point2::new(9, 9)), None);
+    assert_eq!((grid.width(), grid.height()), (4, 3));
+    assert_eq!(grid.remove(Point2::new(-1, 5)), Some('b'));
+    assert_eq!(grid.bounds(), Some((Point2::new(0, 3), Point2::new(2, 4))));
+    assert_eq!(grid.remove(Point2::new(7, 7)), None);
+    grid.remove(Point2::new(2, 3));
+    grid.remove(Point2::new(0, 4));
+    assert_eq!(grid.bounds(), None);
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn test_sparse_grid_neighbors() {
+    let grid: SparseGrid<char> = [(Point2::new(0, 0), 'a'), (Point2::new(1, 1), 'b')]
+        .into_iter()
+        .collect();
+    assert_eq!(grid.neighbors_4(Point2::new(0, 0)).len(), 4);
+    assert!(!grid
+        .neighbors_4(Point2::new(0, 0))
+        .contains(&Point2::new(1, 1)));
+    assert!(grid
+        .neighbors_8(Point2::new(0, 0))
+        .contains(&Point2::new(1, 1)));
+    assert_eq!(
+        grid.occupied_neighbors_8(Point2::new(0, 0)),
+        vec![(Point2::new(1, 1), &'b')]
+    );
+}
+
+#[test]
+fn test_sparse_grid_render_and_dense() {
+    let dense: SquareGrid<char> = "#..\n.#.\n..#".parse().unwrap();
+    let mut grid = SparseGrid::from_dense(dense.clone(), |c| *c == '.');
+    assert_eq!(grid.len(), 3);
+    assert_eq!(grid.to_string(), "#..\n.#.\n..#");
+    assert_eq!(grid.to_dense('.'), (dense, Point2::new(0, 0)));
+    grid.insert(Point2::new(-2, -1), '@');
+    assert_eq!(grid.render(' '), "@    \n  #  \n   # \n    #");
+    assert_eq!(
+        grid.render_region(Point2::new(0, 0), Point2::new(1, 1), '?'),
+        "#?\n?#"
+    );
+    let (dense, origin) = grid.to_dense(' ');
+    assert_eq!(origin, Point2::new(-2, -1));
+    assert_eq!(dense.get(4, 3), Some(&'#'));
+}
@@ -0,0 +1,101 @@
+use itertools::Itertools;
+
+use super::{Direction, Direction8, Point2, SquareGrid};
+
+// an infinite plane covered by copies of one grid, tile (0, 0) is the grid itself
+#[derive(Debug, Clone, Copy)]
+pub struct TiledGrid<'a, T> {
+    grid: &'a SquareGrid<T>,
+}
+
+impl<'a, T> TiledGrid<'a, T> {
+    pub fn new(grid: &'a SquareGrid<T>) -> Self {
+        TiledGrid { grid }
+    }
+
+    fn size(&self) -> Option<Point2<i64>> {
+        (self.grid.width > 0 && self.grid.height > 0)
+            .then(|| Point2::new(self.grid.width as i64, self.grid.height as i64))
+    }
+
+    // position inside the tile, always within the underlying grid
+    pub fn wrap(&self, p: Point2<i64>) -> Option<Point2<usize>> {
+        let size = self.size()?;
+        Some(Point2::new(
+            p.x.rem_euclid(size.x) as usize,
+            p.y.rem_euclid(size.y) as usize,
+        ))
+    }
+
+    pub fn tile(&self, p: Point2<i64>) -> Option<Point2<i64>> {
+        let size = self.size()?;
+        Some(Point2::new(p.x.div_euclid(size.x), p.y.div_euclid(size.y)))
+    }
+
+    pub fn get(&self, p: Point2<i64>) -> Option<&'a T> {
+        self.grid.get_point(self.wrap(p)?)
+    }
+
+    pub fn neighbors_4(&self, p: Point2<i64>) -> Vec<(Point2<i64>, &'a T)> {
+        Direction::all()
+            .into_iter()
+            .map(|direction| p + direction.delta())
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+            .collect_vec()
+    }
+
+    pub fn neighbors_8(&self, p: Point2<i64>) -> Vec<(Point2<i64>, &'a T)> {
+        Direction8::all()
+            .into_iter()
+            .map(|direction| p + direction.delta())
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+            .collect_vec()
+    }
+}
+
+impl<T> SquareGrid<T> {
+    pub fn tiled(&self) -> TiledGrid<'_, T> {
+        TiledGrid::new(self)
+    }
+}
+
+#[test]
+fn test_tiled_grid_wrapping() {
+    let grid: SquareGrid<char> = "abc\ndef".parse().unwrap();
+    let tiled = grid.tiled();
+    assert_eq!(tiled.get(Point2::new(0, 0)), Some(&'a'));
+    assert_eq!(tiled.get(Point2::new(-1, 0)), Some(&'c'));
+    assert_eq!(tiled.get(Point2::new(-3, -2)), Some(&'a'));
+    assert_eq!(tiled.get(Point2::new(-4, -1)), Some(&'f'));
+    assert_eq!(tiled.get(Point2::new(3, 2)), Some(&'a'));
+    assert_eq!(tiled.get(Point2::new(-301, 7)), Some(&'f'));
+    assert_eq!(tiled.wrap(Point2::new(-1, -1)), Some(Point2::new(2, 1)));
+    let empty: SquareGrid<char> = "".parse().unwrap();
+    assert_eq!(empty.tiled().get(Point2::new(0, 0)), None);
+    assert_eq!(empty.tiled().tile(Point2::new(0, 0)), None);
+}
+
+#[test]
+fn test_tiled_grid_tiles() {
+    let grid: SquareGrid<char> = "abc\ndef".parse().unwrap();
+    let tiled = grid.tiled();
+    assert_eq!(tiled.tile(Point2::new(0, 0)), Some(Point2::new(0, 0)));
+    assert_eq!(tiled.tile(Point2::new(2, 1)), Some(Point2::new(0, 0)));
+    assert_eq!(tiled.tile(Point2::new(3, 2)), Some(Point2::new(1, 1)));
+    assert_eq!(tiled.tile(Point2::new(-1, -1)), Some(Point2::new(-1, -1)));
+    assert_eq!(tiled.tile(Point2::new(-3, -2)), Some(Point2::new(-1, -1)));
+    assert_eq!(tiled.tile(Point2::new(-4, -3)), Some(Point2::new(-2, -2)));
+}
+
+#[test]
+fn test_tiled_grid_neighbors() {
+    let grid: SquareGrid<char> = "abc\ndef".parse().unwrap();
+    let tiled = grid.tiled();
+    let neighbors = tiled.neighbors_4(Point2::new(0, 0));
+    assert_eq!(neighbors.len(), 4);
+    assert!(neighbors.contains(&(Point2::new(-1, 0), &'c')));
+    assert!(neighbors.contains(&(Point2::new(0, -1), &'d')));
+    assert!(tiled
+        .neighbors_8(Point2::new(0, 0))
+        .contains(&(Point2::new(-1, -1), &'f')));
+}
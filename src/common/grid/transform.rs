@@ -0,0 +1,170 @@
+use itertools::Itertools;
+
+use super::SquareGrid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> SquareGrid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        SquareGrid {
+            width,
+            height,
+            data: (0..height)
+                .cartesian_product(0..width)
+                .map(|(y, x)| f(x, y))
+                .collect_vec(),
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.data[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn col(&self, x: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        (x < self.width).then(|| self.data.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).filter_map(|x| self.col(x))
+    }
+
+    pub fn iter_with_pos(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (self.get_pos(i), value))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> SquareGrid<U> {
+        SquareGrid {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(&mut f).collect_vec(),
+        }
+    }
+}
+
+impl<T: Clone> SquareGrid<T> {
+    fn cell(&self, x: usize, y: usize) -> T {
+        self.data[y * self.width + x].clone()
+    }
+
+    pub fn transpose(&self) -> Self {
+        SquareGrid::from_fn(self.height, self.width, |x, y| self.cell(y, x))
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        SquareGrid::from_fn(self.height, self.width, |x, y| {
+            self.cell(y, self.height - 1 - x)
+        })
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        SquareGrid::from_fn(self.height, self.width, |x, y| {
+            self.cell(self.width - 1 - y, x)
+        })
+    }
+
+    // mirrors left to right
+    pub fn flip_h(&self) -> Self {
+        SquareGrid::from_fn(self.width, self.height, |x, y| {
+            self.cell(self.width - 1 - x, y)
+        })
+    }
+
+    // mirrors top to bottom
+    pub fn flip_v(&self) -> Self {
+        SquareGrid::from_fn(self.width, self.height, |x, y| {
+            self.cell(x, self.height - 1 - y)
+        })
+    }
+
+    pub fn sub_grid(&self, rect: Rect) -> Option<Self> {
+        if rect.x + rect.width > self.width || rect.y + rect.height > self.height {
+            return None;
+        }
+        Some(SquareGrid::from_fn(rect.width, rect.height, |x, y| {
+            self.cell(rect.x + x, rect.y + y)
+        }))
+    }
+}
+
+#[test]
+fn test_grid_views() {
+    let grid: SquareGrid<char> = "abc\ndef".parse().unwrap();
+    assert_eq!(
+        grid.rows().collect_vec(),
+        vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+    );
+    assert_eq!(
+        grid.cols().map(|col| col.collect::<String>()).collect_vec(),
+        vec!["ad", "be", "cf"]
+    );
+    assert!(grid.row(2).is_none());
+    assert!(grid.col(3).is_none());
+    assert_eq!(grid.iter_with_pos().nth(4), Some(((1, 1), &'e')));
+    assert_eq!(
+        grid.map(|c| c.to_ascii_uppercase()).to_string(),
+        "ABC\nDEF\n"
+    );
+    assert_eq!(
+        grid.sub_grid(Rect::new(1, 0, 2, 2)).unwrap().to_string(),
+        "bc\nef\n"
+    );
+    assert!(grid.sub_grid(Rect::new(2, 0, 2, 2)).is_none());
+}
+
+#[test]
+fn test_grid_transforms() {
+    let grid: SquareGrid<char> = "abc\ndef".parse().unwrap();
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+    assert_eq!(grid.flip_h().to_string(), "cba\nfed\n");
+    assert_eq!(grid.flip_v().to_string(), "def\nabc\n");
+}
+
+#[test]
+fn test_grid_transforms_compose() {
+    for (width, height) in (0..5).cartesian_product(0..5) {
+        let grid = SquareGrid::from_fn(width, height, |x, y| (x, y));
+        let id = grid.clone();
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), id);
+        assert_eq!(grid.rotate_ccw().rotate_ccw().rotate_ccw().rotate_ccw(), id);
+        assert_eq!(grid.rotate_cw().rotate_ccw(), id);
+        assert_eq!(grid.transpose().transpose(), id);
+        assert_eq!(grid.flip_h().flip_h(), id);
+        assert_eq!(grid.flip_v().flip_v(), id);
+        assert_eq!(grid.rotate_cw(), grid.transpose().flip_h());
+        assert_eq!(grid.rotate_ccw(), grid.transpose().flip_v());
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_h().flip_v());
+        assert_eq!(
+            grid.sub_grid(Rect::new(0, 0, width, height)),
+            Some(id.clone())
+        );
+        assert_eq!(grid.map(|p| *p), id);
+        assert_eq!(grid.rows().count(), height);
+        assert_eq!(grid.cols().count(), width);
+    }
+}
@@ -0,0 +1,200 @@
+use std::ops::{Index, IndexMut};
+
+use itertools::Itertools;
+
+pub use self::point::{Point3, Vector3};
+pub use self::sparse::SparseGrid3;
+
+mod point;
+mod sparse;
+
+// x, y and z all start at 0, cells are stored x first, then y, then z
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoxelGrid<T> {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    pub data: Vec<T>,
+}
+
+impl<T> VoxelGrid<T> {
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        depth: usize,
+        mut f: impl FnMut(Point3<usize>) -> T,
+    ) -> Self {
+        let data = itertools::iproduct!(0..depth, 0..height, 0..width)
+            .map(|(z, y, x)| f(Point3::new(x, y, z)))
+            .collect_vec();
+        VoxelGrid {
+            width,
+            height,
+            depth,
+            data,
+        }
+    }
+
+    pub fn new(width: usize, height: usize, depth: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(width, height, depth, |_| value.clone())
+    }
+
+    pub fn contains(&self, p: Point3<usize>) -> bool {
+        p.x < self.width && p.y < self.height && p.z < self.depth
+    }
+
+    fn index_of(&self, p: Point3<usize>) -> Option<usize> {
+        self.contains(p)
+            .then(|| (p.z * self.height + p.y) * self.width + p.x)
+    }
+
+    pub fn get(&self, p: Point3<usize>) -> Option<&T> {
+        self.index_of(p).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point3<usize>) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.data[i])
+    }
+
+    pub fn set(&mut self, p: Point3<usize>, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point3<usize>> {
+        let (width, height) = (self.width, self.height);
+        (0..self.data.len())
+            .map(move |i| Point3::new(i % width, i / width % height, i / width / height))
+    }
+
+    pub fn iter_with_pos(&self) -> impl Iterator<Item = (Point3<usize>, &T)> {
+        self.points().zip(self.data.iter())
+    }
+
+    fn step(&self, p: Point3<usize>, delta: Vector3<i64>) -> Option<Point3<usize>> {
+        let p = p.try_cast::<i64>()? + delta;
+        p.try_cast::<usize>().filter(|p| self.contains(*p))
+    }
+
+    pub fn neighbors_6(&self, p: Point3<usize>) -> Vec<Point3<usize>> {
+        Point3::face_deltas()
+            .into_iter()
+            .filter_map(|delta| self.step(p, delta))
+            .collect_vec()
+    }
+
+    pub fn neighbors_26(&self, p: Point3<usize>) -> Vec<Point3<usize>> {
+        Point3::all_deltas()
+            .into_iter()
+            .filter_map(|delta| self.step(p, delta))
+            .collect_vec()
+    }
+
+    // the passable cells connected through faces to a passable cell on the outside of the box
+    pub fn reachable_from_border(&self, passable: impl Fn(&T) -> bool) -> VoxelGrid<bool> {
+        let mut reached = VoxelGrid::new(self.width, self.height, self.depth, false);
+        let on_border = |p: &Point3<usize>| {
+            [(p.x, self.width), (p.y, self.height), (p.z, self.depth)]
+                .into_iter()
+                .any(|(v, size)| v == 0 || v + 1 == size)
+        };
+        let mut queue = self
+            .iter_with_pos()
+            .filter(|(p, value)| on_border(p) && passable(value))
+            .map(|(p, _)| p)
+            .collect_vec();
+        for p in &queue {
+            reached[*p] = true;
+        }
+        while let Some(p) = queue.pop() {
+            for n in self.neighbors_6(p) {
+                if !reached[n] && passable(&self[n]) {
+                    reached[n] = true;
+                    queue.push(n);
+                }
+            }
+        }
+        reached
+    }
+
+    // faces of solid cells that touch a non-solid cell or the outside of the box
+    pub fn surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        self.count_faces(&is_solid, |n| n.is_none_or(|n| !is_solid(&self[n])))
+    }
+
+    // like `surface_area`, but air pockets sealed inside the solid don't count
+    pub fn exterior_surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        let outside = self.reachable_from_border(|value| !is_solid(value));
+        self.count_faces(&is_solid, |n| n.is_none_or(|n| outside[n]))
+    }
+
+    fn count_faces(
+        &self,
+        is_solid: impl Fn(&T) -> bool,
+        exposed: impl Fn(Option<Point3<usize>>) -> bool,
+    ) -> usize {
+        self.iter_with_pos()
+            .filter(|(_, value)| is_solid(value))
+            .map(|(p, _)| {
+                Point3::face_deltas()
+                    .into_iter()
+                    .filter(|delta| exposed(self.step(p, *delta)))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl<T> Index<Point3<usize>> for VoxelGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point3<usize>) -> &Self::Output {
+        self.get(p).expect("point outside of the grid")
+    }
+}
+
+impl<T> IndexMut<Point3<usize>> for VoxelGrid<T> {
+    fn index_mut(&mut self, p: Point3<usize>) -> &mut Self::Output {
+        self.get_mut(p).expect("point outside of the grid")
+    }
+}
+
+#[test]
+fn test_voxel_grid_access() {
+    let mut grid = VoxelGrid::from_fn(2, 3, 4, |p| p.x + 10 * p.y + 100 * p.z);
+    assert_eq!(grid.data.len(), 24);
+    assert_eq!(grid.get(Point3::new(1, 2, 3)), Some(&321));
+    assert_eq!(grid.get(Point3::new(2, 0, 0)), None);
+    assert!(grid.set(Point3::new(0, 1, 2), 7));
+    assert_eq!(grid[Point3::new(0, 1, 2)], 7);
+    assert!(grid
+        .iter_with_pos()
+        .all(|(p, v)| p == Point3::new(0, 1, 2) || *v == p.x + 10 * p.y + 100 * p.z));
+    assert_eq!(grid.neighbors_6(Point3::new(0, 0, 0)).len(), 3);
+    assert_eq!(grid.neighbors_26(Point3::new(0, 0, 0)).len(), 7);
+    assert_eq!(grid.neighbors_26(Point3::new(1, 1, 1)).len(), 17);
+}
+
+#[test]
+fn test_voxel_grid_surface() {
+    // a 3x3x3 cube with a hollow center
+    let grid = VoxelGrid::from_fn(3, 3, 3, |p| p != Point3::new(1, 1, 1));
+    assert_eq!(grid.surface_area(|solid| *solid), 54 + 6);
+    assert_eq!(grid.exterior_surface_area(|solid| *solid), 54);
+    let outside = grid.reachable_from_border(|solid| !solid);
+    assert!(outside.data.iter().all(|reached| !reached));
+    let open = VoxelGrid::new(2, 2, 2, false);
+    assert!(open
+        .reachable_from_border(|solid| !solid)
+        .data
+        .iter()
+        .all(|reached| *reached));
+}
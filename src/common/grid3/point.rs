@@ -0,0 +1,194 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use itertools::Itertools;
+use num::{Num, Signed};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+pub type Vector3<T> = Point3<T>;
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        Some(Point3::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+            U::try_from(self.z).ok()?,
+        ))
+    }
+}
+
+fn abs_diff<T: Num + PartialOrd>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Num + PartialOrd + Copy> Point3<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn component_min(self, other: Self) -> Self {
+        let min = |a: T, b: T| if a < b { a } else { b };
+        Point3::new(
+            min(self.x, other.x),
+            min(self.y, other.y),
+            min(self.z, other.z),
+        )
+    }
+
+    pub fn component_max(self, other: Self) -> Self {
+        let max = |a: T, b: T| if a > b { a } else { b };
+        Point3::new(
+            max(self.x, other.x),
+            max(self.y, other.y),
+            max(self.z, other.z),
+        )
+    }
+}
+
+impl<T: Signed + Copy> Point3<T> {
+    // unit steps along each axis, the points sharing a face with a cube
+    pub fn face_deltas() -> [Vector3<T>; 6] {
+        let (zero, one) = (T::zero(), T::one());
+        [
+            Point3::new(one, zero, zero),
+            Point3::new(-one, zero, zero),
+            Point3::new(zero, one, zero),
+            Point3::new(zero, -one, zero),
+            Point3::new(zero, zero, one),
+            Point3::new(zero, zero, -one),
+        ]
+    }
+
+    // every combination of -1, 0 and 1 apart from zero, so faces, edges and corners
+    pub fn all_deltas() -> Vec<Vector3<T>> {
+        let steps = [-T::one(), T::zero(), T::one()];
+        itertools::iproduct!(steps, steps, steps)
+            .map(|(x, y, z)| Point3::new(x, y, z))
+            .filter(|p| !p.is_zero())
+            .collect_vec()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero() && self.z.is_zero()
+    }
+
+    pub fn neighbors_6(self) -> [Self; 6] {
+        Self::face_deltas().map(|delta| self + delta)
+    }
+
+    pub fn neighbors_26(self) -> Vec<Self> {
+        Self::all_deltas()
+            .into_iter()
+            .map(|delta| self + delta)
+            .collect_vec()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[test]
+fn test_point3_arithmetic() {
+    let a = Point3::new(3, -2, 1);
+    let b = Point3::new(-1, 5, 4);
+    assert_eq!(a + b, Point3::new(2, 3, 5));
+    assert_eq!(a - b, Point3::new(4, -7, -3));
+    assert_eq!(a * 2, Point3::new(6, -4, 2));
+    assert_eq!(-a, Point3::new(-3, 2, -1));
+    assert_eq!(a.manhattan_distance(&b), 14);
+    assert_eq!(a.component_min(b), Point3::new(-1, -2, 1));
+    assert_eq!(a.component_max(b), Point3::new(3, 5, 4));
+    assert_eq!(Point3::from((1, 2, 3)).to_string(), "(1, 2, 3)");
+}
+
+#[test]
+fn test_point3_neighbors() {
+    let p = Point3::new(1i64, 1, 1);
+    let faces = p.neighbors_6();
+    assert!(faces.iter().all(|n| n.manhattan_distance(&p) == 1));
+    assert_eq!(faces.iter().unique().count(), 6);
+    let all = p.neighbors_26();
+    assert_eq!(all.len(), 26);
+    assert!(!all.contains(&p));
+    assert!(all.contains(&Point3::new(0, 2, 0)));
+    assert!(faces.iter().all(|n| all.contains(n)));
+}
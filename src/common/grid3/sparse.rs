@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use super::{Point3, VoxelGrid};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid3<T> {
+    cells: HashMap<Point3<i64>, T>,
+    bounds: Option<(Point3<i64>, Point3<i64>)>,
+}
+
+impl<T> Default for SparseGrid3<T> {
+    fn default() -> Self {
+        SparseGrid3 {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid3<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, p: Point3<i64>) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point3<i64>) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn insert(&mut self, p: Point3<i64>, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.component_min(p), max.component_max(p)),
+            None => (p, p),
+        });
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point3<i64>) -> Option<T> {
+        let removed = self.cells.remove(&p);
+        // only a cell on the surface of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds {
+            let on_edge = [
+                (p.x, min.x, max.x),
+                (p.y, min.y, max.y),
+                (p.z, min.z, max.z),
+            ]
+            .into_iter()
+            .any(|(v, min, max)| v == min || v == max);
+            if removed.is_some() && on_edge {
+                self.bounds = self.cells.keys().fold(None, |bounds, p| {
+                    Some(match bounds {
+                        Some((min, max)) => (p.component_min(min), p.component_max(max)),
+                        None => (*p, *p),
+                    })
+                });
+            }
+        }
+        removed
+    }
+
+    // inclusive lowest and highest corners of the occupied cells
+    pub fn bounds(&self) -> Option<(Point3<i64>, Point3<i64>)> {
+        self.bounds
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point3<i64>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // the dense grid starts at the lowest corner of the bounds, which is returned alongside it
+    pub fn to_dense<U>(&self, mut f: impl FnMut(Option<&T>) -> U) -> (VoxelGrid<U>, Point3<i64>) {
+        let Some((min, max)) = self.bounds else {
+            return (VoxelGrid::from_fn(0, 0, 0, |_| f(None)), Point3::default());
+        };
+        let size = (max - min + Point3::new(1, 1, 1))
+            .try_cast::<usize>()
+            .expect("bounds are ordered");
+        let grid = VoxelGrid::from_fn(size.x, size.y, size.z, |p| {
+            f(self.get(min + p.try_cast::<i64>().expect("grid fits in i64")))
+        });
+        (grid, min)
+    }
+
+    // faces of occupied cells that don't touch another occupied cell
+    pub fn surface_area(&self) -> usize {
+        self.points()
+            .map(|p| {
+                p.neighbors_6()
+                    .into_iter()
+                    .filter(|n| !self.contains(*n))
+                    .count()
+            })
+            .sum()
+    }
+
+    // like `surface_area`, but faces of air pockets sealed inside don't count
+    pub fn exterior_surface_area(&self) -> usize {
+        let (grid, _) = self.to_dense(|value| value.is_some());
+        grid.exterior_surface_area(|solid| *solid)
+    }
+}
+
+impl<T> FromIterator<(Point3<i64>, T)> for SparseGrid3<T> {
+    fn from_iter<I: IntoIterator<Item = (Point3<i64>, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid3::new();
+        for (p, value) in iter {
+            grid.insert(p, value);
+        }
+        grid
+    }
+}
+
+#[test]
+fn test_sparse_grid3_bounds() {
+    let mut grid = SparseGrid3::new();
+    assert_eq!(grid.bounds(), None);
+    grid.insert(Point3::new(2, 3, -1), 'a');
+    grid.insert(Point3::new(-1, 5, 0), 'b');
+    grid.insert(Point3::new(0, 4, 4), 'c');
+    assert_eq!(
+        grid.bounds(),
+        Some((Point3::new(-1, 3, -1), Point3::new(2, 5, 4)))
+    );
+    assert_eq!(grid.remove(Point3::new(0, 4, 4)), Some('c'));
+    assert_eq!(
+        grid.bounds(),
+        Some((Point3::new(-1, 3, -1), Point3::new(2, 5, 0)))
+    );
+    let (dense, origin) = grid.to_dense(|value| value.copied());
+    assert_eq!(origin, Point3::new(-1, 3, -1));
+    assert_eq!((dense.width, dense.height, dense.depth), (4, 3, 2));
+    assert_eq!(dense[Point3::new(3, 0, 0)], Some('a'));
+    assert_eq!(dense.data.iter().flatten().count(), 2);
+}
+
+#[test]
+fn test_sparse_grid3_surface() {
+    let two: SparseGrid3<()> = [(Point3::new(1, 1, 1), ()), (Point3::new(2, 1, 1), ())]
+        .into_iter()
+        .collect();
+    assert_eq!(two.surface_area(), 10);
+    assert_eq!(two.exterior_surface_area(), 10);
+    // a hollow 3x3x3 cube, shifted away from the origin
+    let hollow: SparseGrid3<()> = itertools::iproduct!(-1..2, 5..8, 0..3)
+        .map(|(x, y, z)| Point3::new(x, y, z))
+        .filter(|p| *p != Point3::new(0, 6, 1))
+        .map(|p| (p, ()))
+        .collect();
+    assert_eq!(hollow.surface_area(), 60);
+    assert_eq!(hollow.exterior_surface_area(), 54);
+}
@@ -0,0 +1,392 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use num::Num;
+
+// half-open, so `end` itself is not included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value < self.end
+    }
+
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Interval::new(
+            self.start.max(other.start),
+            self.end.min(other.end),
+        ))
+        .filter(|i| !i.is_empty())
+    }
+
+    // the parts below `cut` and from `cut` onwards, leaving out the empty ones
+    pub fn split_at(&self, cut: T) -> (Option<Self>, Option<Self>) {
+        let below = Interval::new(self.start, cut.min(self.end));
+        let above = Interval::new(cut.max(self.start), self.end);
+        (
+            Some(below).filter(|i| !i.is_empty()),
+            Some(above).filter(|i| !i.is_empty()),
+        )
+    }
+}
+
+impl<T: Copy + Ord + Num> Interval<T> {
+    pub fn from_inclusive(start: T, end: T) -> Self {
+        Interval::new(start, end + T::one())
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+// sorted and non-overlapping, touching intervals are merged
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+
+    fn normalized(intervals: impl IntoIterator<Item = Interval<T>>) -> Self {
+        let mut merged: Vec<Interval<T>> = vec![];
+        for interval in intervals.into_iter().filter(|i| !i.is_empty()).sorted() {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = Self::normalized(self.intervals.iter().copied().chain([interval]));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.intervals.iter().chain(&other.intervals).copied())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::normalized(
+            self.intervals
+                .iter()
+                .cartesian_product(&other.intervals)
+                .filter_map(|(a, b)| a.intersection(b)),
+        )
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut remaining = self.intervals.clone();
+        for cut in &other.intervals {
+            remaining = remaining
+                .into_iter()
+                .flat_map(|i| {
+                    let (below, _) = i.split_at(cut.start);
+                    let (_, above) = i.split_at(cut.end);
+                    [below, above]
+                })
+                .flatten()
+                .collect_vec();
+        }
+        Self::normalized(remaining)
+    }
+}
+
+impl<T: Copy + Ord + Num> IntervalSet<T> {
+    pub fn total_len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |total, i| total + i.len())
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::normalized(iter)
+    }
+}
+
+// moves every value inside a source interval by the same amount, other values stay put
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OffsetMap<T> {
+    // source intervals with the value their start maps to
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Copy + Ord + Num> OffsetMap<T> {
+    pub fn new() -> Self {
+        OffsetMap { pieces: vec![] }
+    }
+
+    // sources are expected not to overlap, the first one added wins where they do
+    pub fn insert(&mut self, source: Interval<T>, destination_start: T) {
+        self.pieces.push((source, destination_start));
+    }
+
+    pub fn map_value(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                value - source.start + *destination
+            })
+    }
+
+    // the images of `interval`, one per piece of the table it passes through
+    pub fn map_interval(&self, interval: Interval<T>) -> Vec<Interval<T>> {
+        let mut unmapped = vec![interval];
+        let mut mapped = vec![];
+        for (source, destination) in &self.pieces {
+            unmapped = unmapped
+                .into_iter()
+                .flat_map(|i| {
+                    if let Some(overlap) = i.intersection(source) {
+                        let start = overlap.start - source.start + *destination;
+                        mapped.push(Interval::new(start, start + overlap.len()));
+                    }
+                    let (below, _) = i.split_at(source.start);
+                    let (_, above) = i.split_at(source.end);
+                    [below, above]
+                })
+                .flatten()
+                .collect_vec();
+        }
+        mapped.extend(unmapped);
+        mapped
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.intervals()
+            .iter()
+            .flat_map(|i| self.map_interval(*i))
+            .collect()
+    }
+}
+
+// an N-dimensional box made of one interval per axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const N: usize> {
+    pub axes: [Interval<T>; N],
+}
+
+impl<T: Copy + Ord, const N: usize> IntervalBox<T, N> {
+    pub fn new(axes: [Interval<T>; N]) -> Self {
+        IntervalBox { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(axis, v)| axis.contains(v))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(IntervalBox { axes })
+    }
+
+    // the parts with `axis` below `cut` and from `cut` onwards, leaving out the empty ones
+    pub fn split_at(&self, axis: usize, cut: T) -> (Option<Self>, Option<Self>) {
+        let (below, above) = self.axes[axis].split_at(cut);
+        let with_axis = |interval: Interval<T>| {
+            let mut axes = self.axes;
+            axes[axis] = interval;
+            IntervalBox { axes }
+        };
+        (below.map(with_axis), above.map(with_axis))
+    }
+}
+
+impl<T: Copy + Ord + Num, const N: usize> IntervalBox<T, N> {
+    pub fn volume(&self) -> T {
+        self.axes
+            .iter()
+            .fold(T::one(), |volume, axis| volume * axis.len())
+    }
+}
+
+#[test]
+fn test_interval() {
+    let a = Interval::new(2, 6);
+    assert_eq!(a.len(), 4);
+    assert!(a.contains(&2) && a.contains(&5) && !a.contains(&6));
+    assert!(a.contains_interval(&Interval::new(3, 6)));
+    assert!(!a.contains_interval(&Interval::new(3, 7)));
+    assert!(a.overlaps(&Interval::new(5, 9)));
+    assert!(!a.overlaps(&Interval::new(6, 9)));
+    assert_eq!(
+        a.intersection(&Interval::new(4, 9)),
+        Some(Interval::new(4, 6))
+    );
+    assert_eq!(
+        a.split_at(4),
+        (Some(Interval::new(2, 4)), Some(Interval::new(4, 6)))
+    );
+    assert_eq!(a.split_at(2), (None, Some(a)));
+    assert_eq!(a.split_at(10), (Some(a), None));
+    assert_eq!(Interval::from_inclusive(3, 3).len(), 1);
+    assert_eq!(Interval::new(5, 2).len(), 0);
+    assert_eq!(a.to_string(), "[2, 6)");
+}
+
+#[test]
+fn test_interval_set() {
+    let set: IntervalSet<i32> = [(8, 12), (6, 15), (3, 5), (0, 3), (20, 20)]
+        .into_iter()
+        .map(|(a, b)| Interval::new(a, b))
+        .collect();
+    assert_eq!(
+        set.intervals(),
+        &[Interval::new(0, 5), Interval::new(6, 15)]
+    );
+    assert_eq!(set.total_len(), 14);
+    assert!(set.contains(&4) && !set.contains(&5));
+    let other: IntervalSet<i32> = [Interval::new(4, 7), Interval::new(10, 11)]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        set.intersection(&other).intervals(),
+        &[
+            Interval::new(4, 5),
+            Interval::new(6, 7),
+            Interval::new(10, 11)
+        ]
+    );
+    assert_eq!(
+        set.difference(&other).intervals(),
+        &[
+            Interval::new(0, 4),
+            Interval::new(7, 10),
+            Interval::new(11, 15)
+        ]
+    );
+    assert_eq!(set.union(&other).intervals(), &[Interval::new(0, 15)]);
+    assert!(set.difference(&set.union(&other)).is_empty());
+    assert!(!set.intersection(&other).is_empty());
+    let mut set = IntervalSet::new();
+    assert!(set.is_empty());
+    set.insert(Interval::new(1, 2));
+    set.insert(Interval::new(-3, 1));
+    assert_eq!(set.intervals(), &[Interval::new(-3, 2)]);
+}
+
+#[test]
+fn test_offset_map() {
+    let mut map = OffsetMap::new();
+    map.insert(Interval::new(98u64, 100), 50);
+    map.insert(Interval::new(50, 98), 52);
+    assert_eq!(map.map_value(79), 81);
+    assert_eq!(map.map_value(99), 51);
+    assert_eq!(map.map_value(14), 14);
+    let mapped: IntervalSet<u64> = map
+        .map_interval(Interval::new(40, 100))
+        .into_iter()
+        .collect();
+    // 50..98 moves up by 2 and 98..100 fills the gap left at 50..52
+    assert_eq!(mapped.intervals(), &[Interval::new(40, 100)]);
+    assert_eq!(
+        map.map_interval(Interval::new(97, 99)),
+        vec![Interval::new(50, 51), Interval::new(99, 100)]
+    );
+    // every value maps to the same place as it does on its own
+    for start in 40..105 {
+        for end in start..105 {
+            let images: IntervalSet<u64> = map
+                .map_interval(Interval::new(start, end))
+                .into_iter()
+                .collect();
+            let expected: IntervalSet<u64> = (start..end)
+                .map(|v| Interval::new(map.map_value(v), map.map_value(v) + 1))
+                .collect();
+            assert_eq!(images, expected);
+        }
+    }
+}
+
+#[test]
+fn test_interval_box() {
+    let cube = IntervalBox::new([Interval::new(1u64, 4001); 4]);
+    assert_eq!(cube.volume(), 4000u64.pow(4));
+    let (below, above) = cube.split_at(1, 1001);
+    assert_eq!(
+        below.unwrap().volume() + above.unwrap().volume(),
+        cube.volume()
+    );
+    assert_eq!(below.unwrap().axes[1], Interval::new(1, 1001));
+    assert!(cube.contains(&[1, 2, 3, 4000]));
+    assert!(!cube.contains(&[0, 2, 3, 4]));
+    assert_eq!(cube.split_at(0, 1), (None, Some(cube)));
+    assert!(!cube.is_empty());
+    let flat = IntervalBox::new([Interval::new(0, 2), Interval::new(3, 3)]);
+    assert!(flat.is_empty());
+    assert_eq!(flat.volume(), 0);
+    let small = IntervalBox::new([Interval::new(0, 2), Interval::new(3, 5)]);
+    assert_eq!(
+        small.intersection(&IntervalBox::new([
+            Interval::new(1, 9),
+            Interval::new(0, 4)
+        ])),
+        Some(IntervalBox::new([Interval::new(1, 2), Interval::new(3, 4)]))
+    );
+    assert_eq!(
+        small.intersection(&IntervalBox::new([
+            Interval::new(2, 9),
+            Interval::new(0, 4)
+        ])),
+        None
+    );
+}
@@ -0,0 +1,126 @@
+use itertools::Itertools;
+
+// (g, x, y) with a * x + b * y = g = gcd(a, b)
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// the x in 0..m with a * x = 1 (mod m), if a and m are coprime
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+// combines x = r (mod m) for every (r, m) into a single (r, m) with m the lcm of the moduli,
+// or None if they contradict each other, the moduli don't need to be coprime
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (r, m) = congruences
+        .iter()
+        .try_fold((0i128, 1i128), |(r1, m1), &(r2, m2)| {
+            let (r2, m2) = ((r2 as i128).rem_euclid(m2 as i128), m2 as i128);
+            let (g, p, _) = extended_gcd_wide(m1, m2);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+            let step = m2 / g;
+            let k = ((r2 - r1) / g % step * p).rem_euclid(step);
+            let m = m1 * step;
+            Some(((r1 + m1 * k).rem_euclid(m), m))
+        })?;
+    Some((i64::try_from(r).ok()?, i64::try_from(m).ok()?))
+}
+
+// the first time at or after every offset that is `offset + n * period` for each
+// (offset, period), like when several things that start repeating at different times line up
+pub fn first_alignment(cycles: &[(u64, u64)]) -> Option<u64> {
+    let congruences = cycles
+        .iter()
+        .map(|&(offset, period)| (offset as i64, period as i64))
+        .collect_vec();
+    let (r, m) = crt(&congruences)?;
+    let (r, m) = (r as u64, m as u64);
+    let earliest = cycles.iter().map(|(offset, _)| *offset).max().unwrap_or(0);
+    Some(if r >= earliest {
+        r
+    } else {
+        r + (earliest - r).div_ceil(m) * m
+    })
+}
+
+#[test]
+fn test_extended_gcd() {
+    for (a, b) in [(240, 46), (46, 240), (17, 5), (0, 7), (12, -18), (1, 1)] {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, num::integer::gcd(a, b));
+        assert_eq!(a * x + b * y, g);
+    }
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(-3, 11), Some(7));
+    assert_eq!(mod_inverse(6, 9), None);
+    assert_eq!(mod_pow(4, 13, 497), 445);
+    assert_eq!(mod_pow(2, 0, 1), 0);
+    assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    // not coprime, but consistent
+    assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+    assert_eq!(crt(&[]), Some((0, 1)));
+}
+
+#[test]
+fn test_first_alignment() {
+    // offsets equal to the periods just give the lcm
+    assert_eq!(first_alignment(&[(2, 2), (3, 3)]), Some(6));
+    assert_eq!(first_alignment(&[(1, 2), (0, 3)]), Some(3));
+    // the answer must not come before a cycle has started
+    assert_eq!(first_alignment(&[(10, 3), (1, 1)]), Some(10));
+    assert_eq!(first_alignment(&[(7, 5), (2, 5)]), Some(7));
+    assert_eq!(first_alignment(&[(7, 5), (3, 5)]), None);
+    for (a, b) in [((3, 4), (5, 6)), ((13, 5), (4, 9)), ((100, 7), (2, 3))] {
+        let t = first_alignment(&[a, b]).unwrap();
+        let aligned = |t: u64| {
+            [a, b]
+                .iter()
+                .all(|(o, p)| t >= *o && (t - o).is_multiple_of(*p))
+        };
+        assert!(aligned(t));
+        assert!(!(0..t).any(aligned));
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    hash::Hash,
+};
+
+// a cache for recursive functions, the computation gets the memo back so it can recurse through it
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    // insertion order, only kept when there's a limit to evict by
+    order: VecDeque<K>,
+    limit: Option<usize>,
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+    pub len: usize,
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.hits + self.misses;
+        let hit_rate = if lookups == 0 {
+            0.0
+        } else {
+            100.0 * self.hits as f64 / lookups as f64
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evictions, {} cached",
+            self.hits, self.misses, hit_rate, self.evictions, self.len
+        )
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            limit: None,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    // keeps at most `limit` values, dropping the oldest first
+    pub fn with_limit(limit: usize) -> Self {
+        Memo {
+            limit: Some(limit),
+            ..Self::new()
+        }
+    }
+
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        let Some(limit) = self.limit else {
+            self.cache.insert(key, value);
+            return;
+        };
+        if limit == 0 {
+            return;
+        }
+        // a recursive computation can have filled in its own key already
+        if self.cache.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.cache.len() > limit {
+            let oldest = self.order.pop_front().unwrap();
+            self.cache.remove(&oldest);
+            self.evictions += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.order.clear();
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            len: self.cache.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    memo.get_or_compute(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+}
+
+#[test]
+fn test_memo() {
+    let mut memo = Memo::new();
+    assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+    // every n from 2 to 90 is computed once and looked up again once, apart from the last two
+    assert_eq!(
+        memo.stats(),
+        MemoStats {
+            hits: 87,
+            misses: 89,
+            evictions: 0,
+            len: 89,
+        }
+    );
+    assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+    assert_eq!(memo.stats().hits, 88);
+    assert_eq!(memo.get(&10), Some(&55));
+    memo.clear();
+    assert!(memo.is_empty());
+}
+
+#[test]
+fn test_memo_limit() {
+    let mut memo = Memo::with_limit(3);
+    assert_eq!(fibonacci(30, &mut memo), 832040);
+    let stats = memo.stats();
+    assert_eq!(memo.len(), 3);
+    assert_eq!(stats.len, memo.len());
+    assert_eq!(stats.evictions, stats.misses - 3);
+    // the most recent values are the ones kept
+    assert_eq!(memo.get(&30), Some(&832040));
+    assert_eq!(memo.get(&2), None);
+
+    let mut memo = Memo::with_limit(0);
+    assert_eq!(fibonacci(20, &mut memo), 6765);
+    assert!(memo.is_empty());
+}
@@ -1,7 +1,14 @@
-use std::error::Error;
-
+pub mod bnb;
+pub mod cycle;
 pub mod data;
+pub mod error;
+pub mod graph;
 pub mod grid;
+pub mod grid3;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod poly;
+pub mod puzzle;
+pub mod search;
 pub mod solution;
-
-pub type SimpleResult<T> = std::result::Result<T, Box<dyn Error>>;
@@ -0,0 +1,154 @@
+use itertools::Itertools;
+use num::{BigInt, BigRational, One, Zero};
+
+// the rows of differences, down to the first row that is all zeroes
+fn difference_table(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut rows = vec![values.to_vec()];
+    while rows.last().unwrap().iter().any(|v| *v != 0) {
+        let next = rows
+            .last()
+            .unwrap()
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| b - a)
+            .collect_vec();
+        rows.push(next);
+    }
+    rows
+}
+
+// the value after the last one, assuming the values come from a polynomial
+pub fn extrapolate_forward(values: &[i64]) -> i64 {
+    difference_table(values)
+        .iter()
+        .filter_map(|row| row.last())
+        .sum()
+}
+
+// the value before the first one, assuming the values come from a polynomial
+pub fn extrapolate_backward(values: &[i64]) -> i64 {
+    difference_table(values)
+        .iter()
+        .rev()
+        .filter_map(|row| row.first())
+        .fold(0, |below, first| first - below)
+}
+
+// coefficients are exact fractions, lowest power first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<BigRational>) -> Self {
+        let mut polynomial = Polynomial { coefficients };
+        while polynomial.coefficients.last().is_some_and(Zero::is_zero) {
+            polynomial.coefficients.pop();
+        }
+        polynomial
+    }
+
+    // the lowest degree polynomial through all the points, their x values must all differ
+    pub fn interpolate(points: &[(i64, i64)]) -> Self {
+        let mut coefficients = vec![BigRational::zero(); points.len()];
+        for (i, (xi, yi)) in points.iter().enumerate() {
+            let mut basis = vec![BigRational::one()];
+            let mut denominator = BigInt::one();
+            for (j, (xj, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                // multiply by (x - xj)
+                let root = BigRational::from_integer(BigInt::from(*xj));
+                let mut next = vec![BigRational::zero(); basis.len() + 1];
+                for (k, c) in basis.iter().enumerate() {
+                    next[k + 1] += c;
+                    next[k] -= &root * c;
+                }
+                basis = next;
+                denominator *= BigInt::from(xi - xj);
+            }
+            let scale = BigRational::new(BigInt::from(*yi), denominator);
+            for (c, b) in coefficients.iter_mut().zip(basis) {
+                *c += b * &scale;
+            }
+        }
+        Polynomial::new(coefficients)
+    }
+
+    pub fn coefficients(&self) -> &[BigRational] {
+        &self.coefficients
+    }
+
+    // the zero polynomial counts as degree 0 too
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn eval(&self, x: &BigInt) -> BigRational {
+        let x = BigRational::from_integer(x.clone());
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * &x + c)
+    }
+
+    // None when the value at `x` isn't a whole number
+    pub fn eval_integer(&self, x: impl Into<BigInt>) -> Option<BigInt> {
+        let value = self.eval(&x.into());
+        value.is_integer().then(|| value.to_integer())
+    }
+}
+
+#[test]
+fn test_extrapolate() {
+    // the 2023 day 9 examples
+    let sequences = [
+        [0, 3, 6, 9, 12, 15],
+        [1, 3, 6, 10, 15, 21],
+        [10, 13, 16, 21, 30, 45],
+    ];
+    let forward = sequences.map(|s| extrapolate_forward(&s));
+    let backward = sequences.map(|s| extrapolate_backward(&s));
+    assert_eq!(forward, [18, 28, 68]);
+    assert_eq!(backward, [-3, 0, 5]);
+    assert_eq!(extrapolate_forward(&[]), 0);
+    assert_eq!(extrapolate_forward(&[7]), 7);
+    assert_eq!(extrapolate_backward(&[7]), 7);
+}
+
+#[test]
+fn test_interpolate() {
+    let points = [10, 13, 16, 21, 30, 45]
+        .iter()
+        .enumerate()
+        .map(|(x, y)| (x as i64, *y))
+        .collect_vec();
+    let polynomial = Polynomial::interpolate(&points);
+    assert_eq!(polynomial.degree(), 3);
+    assert_eq!(polynomial.eval_integer(6), Some(BigInt::from(68)));
+    assert_eq!(polynomial.eval_integer(-1), Some(BigInt::from(5)));
+
+    // y = (x^2 - 1) / 2 has non-integer coefficients and values
+    let half = Polynomial::interpolate(&[(1, 0), (3, 4), (5, 12)]);
+    assert_eq!(half.degree(), 2);
+    let ratio = |n: i64, d: i64| BigRational::new(BigInt::from(n), BigInt::from(d));
+    assert_eq!(
+        half.coefficients(),
+        &[ratio(-1, 2), ratio(0, 1), ratio(1, 2)]
+    );
+    assert_eq!(half.eval_integer(7), Some(BigInt::from(24)));
+    assert_eq!(half.eval_integer(2), None);
+    assert_eq!(
+        half.eval(&BigInt::from(2)),
+        BigRational::new(BigInt::from(3), BigInt::from(2))
+    );
+
+    // far past where i64 would overflow
+    let square = Polynomial::interpolate(&[(0, 0), (1, 1), (2, 4)]);
+    let x = BigInt::from(10).pow(20);
+    assert_eq!(square.eval_integer(x.clone()), Some(&x * &x));
+
+    assert_eq!(Polynomial::interpolate(&[(3, 0), (5, 0)]).degree(), 0);
+}
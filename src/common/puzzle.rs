@@ -0,0 +1,164 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use super::error::{AocError, AocResult};
+
+const FIRST_YEAR: u32 = 2015;
+const LAST_YEAR: u32 = 2999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PuzzleId {
+    year: u32,
+    day: u32,
+    part: u32,
+}
+
+impl PuzzleId {
+    pub fn new(year: u32, day: u32, part: u32) -> AocResult<PuzzleId> {
+        let puzzle = PuzzleId { year, day, part };
+        let last_part = if day == days_in_year(year) { 1 } else { 2 };
+        if (FIRST_YEAR..=LAST_YEAR).contains(&year)
+            && (1..=days_in_year(year)).contains(&day)
+            && (1..=last_part).contains(&part)
+            && unlock_time(year, day).is_some()
+        {
+            Ok(puzzle)
+        } else {
+            Err(AocError::InvalidPuzzle { year, day, part })
+        }
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn part(&self) -> u32 {
+        self.part
+    }
+
+    pub fn with_part(&self, part: u32) -> AocResult<PuzzleId> {
+        PuzzleId::new(self.year, self.day, part)
+    }
+
+    pub fn unlock_time(&self) -> DateTime<Utc> {
+        unlock_time(self.year, self.day).expect("PuzzleId::new checks the unlock time exists")
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        Utc::now() >= self.unlock_time()
+    }
+
+    pub fn check_unlocked(&self) -> AocResult<()> {
+        if self.is_unlocked() {
+            Ok(())
+        } else {
+            Err(AocError::NotUnlocked(format!(
+                "{} (unlocks at {})",
+                self,
+                self.unlock_time()
+            )))
+        }
+    }
+}
+
+pub fn days_in_year(year: u32) -> u32 {
+    if year < 2025 {
+        25
+    } else {
+        12
+    }
+}
+
+// Puzzles unlock at midnight EST, which is 05:00 UTC
+fn unlock_time(year: u32, day: u32) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(year.try_into().ok()?, 12, day, 5, 0, 0)
+        .single()
+}
+
+pub fn check_year(year: u32) -> AocResult<()> {
+    PuzzleId::new(year, 1, 1).map(|_| ())
+}
+
+pub fn latest_event_year() -> u32 {
+    let this_year = Utc::now().year() as u32;
+    if unlock_time(this_year, 1).is_some_and(|time| Utc::now() >= time) {
+        this_year
+    } else {
+        this_year - 1
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "y{}d{}p{}", self.year, self.day, self.part)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = || AocError::Parse(format!("puzzle id `{}`", s));
+        let numbers = if let Some(ydp) = s.strip_prefix('y') {
+            let (year, dp) = ydp.split_once('d').ok_or_else(parse_error)?;
+            let (day, part) = dp.split_once('p').ok_or_else(parse_error)?;
+            [year, day, part]
+        } else {
+            let mut parts = s.splitn(3, '-');
+            [(); 3].map(|_| parts.next().unwrap_or(""))
+        };
+        let [year, day, part] = numbers.map(|n| n.parse::<u32>().map_err(|_| parse_error()));
+        PuzzleId::new(year?, day?, part?)
+    }
+}
+
+#[test]
+fn test_puzzle_id_parse() -> AocResult<()> {
+    let expected = PuzzleId::new(2023, 17, 2)?;
+    assert_eq!("2023-17-2".parse::<PuzzleId>()?, expected);
+    assert_eq!("y2023d17p2".parse::<PuzzleId>()?, expected);
+    assert_eq!(expected.to_string(), "y2023d17p2");
+    assert_eq!(expected.to_string().parse::<PuzzleId>()?, expected);
+    assert!("2023-17".parse::<PuzzleId>().is_err());
+    assert!("y2023d17".parse::<PuzzleId>().is_err());
+    assert!("2023-x-1".parse::<PuzzleId>().is_err());
+    assert!("2023-17-2-1".parse::<PuzzleId>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_puzzle_id_validation() {
+    assert!(PuzzleId::new(2014, 1, 1).is_err());
+    assert!(PuzzleId::new(2999, 1, 1).is_ok());
+    assert!(PuzzleId::new(3000, 1, 1).is_err());
+    assert!(matches!(
+        PuzzleId::new(u32::MAX, 1, 1),
+        Err(AocError::InvalidPuzzle { .. })
+    ));
+    assert!(PuzzleId::new(2015, 0, 1).is_err());
+    assert!(PuzzleId::new(2015, 1, 3).is_err());
+    assert!(PuzzleId::new(2024, 25, 1).is_ok());
+    assert!(PuzzleId::new(2024, 25, 2).is_err());
+    assert!(PuzzleId::new(2024, 26, 1).is_err());
+    assert!(PuzzleId::new(2025, 11, 2).is_ok());
+    assert!(PuzzleId::new(2025, 12, 1).is_ok());
+    assert!(PuzzleId::new(2025, 12, 2).is_err());
+    assert!(PuzzleId::new(2025, 13, 1).is_err());
+}
+
+#[test]
+fn test_puzzle_unlock_time() -> AocResult<()> {
+    let puzzle = PuzzleId::new(2023, 17, 1)?;
+    assert_eq!(
+        puzzle.unlock_time().to_rfc3339(),
+        "2023-12-17T05:00:00+00:00"
+    );
+    assert!(puzzle.is_unlocked());
+    assert!(PuzzleId::new(2999, 1, 1)?.check_unlocked().is_err());
+    Ok(())
+}
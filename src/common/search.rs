@@ -0,0 +1,316 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use num::Zero;
+
+// every state reached so far, with its best known cost and the state it was reached from
+struct Explored<S, C> {
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    best: Vec<(C, Option<usize>)>,
+}
+
+impl<S: Hash + Eq + Clone, C: Copy + Ord> Explored<S, C> {
+    fn new() -> Self {
+        Explored {
+            states: vec![],
+            index: HashMap::new(),
+            best: vec![],
+        }
+    }
+
+    // records `state` if it's new or `cost` beats its best cost, returning its id if so
+    fn relax(&mut self, state: S, cost: C, parent: Option<usize>) -> Option<usize> {
+        match self.index.get(&state) {
+            Some(&id) if self.best[id].0 <= cost => None,
+            Some(&id) => {
+                self.best[id] = (cost, parent);
+                Some(id)
+            }
+            None => {
+                let id = self.states.len();
+                self.index.insert(state.clone(), id);
+                self.states.push(state);
+                self.best.push((cost, parent));
+                Some(id)
+            }
+        }
+    }
+
+    fn path_to(&self, mut id: usize) -> Vec<S> {
+        let mut path = vec![self.states[id].clone()];
+        while let Some(parent) = self.best[id].1 {
+            path.push(self.states[parent].clone());
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    fn into_distances(self) -> HashMap<S, C> {
+        self.states
+            .into_iter()
+            .zip(self.best)
+            .map(|(state, (cost, _))| (state, cost))
+            .collect()
+    }
+}
+
+// the cheapest goal found, the path leading to it is only rebuilt when asked for
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    goal: usize,
+    explored: Explored<S, C>,
+}
+
+impl<S: Hash + Eq + Clone, C: Copy + Ord> SearchResult<S, C> {
+    pub fn goal(&self) -> &S {
+        &self.explored.states[self.goal]
+    }
+
+    // from one of the start states to the goal, both included
+    pub fn path(&self) -> Vec<S> {
+        self.explored.path_to(self.goal)
+    }
+}
+
+fn run_bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Explored<S, usize>, Option<usize>)
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut explored = Explored::new();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|start| explored.relax(start, 0, None))
+        .collect();
+    while let Some(id) = queue.pop_front() {
+        let state = explored.states[id].clone();
+        if is_goal(&state) {
+            return (explored, Some(id));
+        }
+        let cost = explored.best[id].0 + 1;
+        for next in successors(&state) {
+            // the first visit is always the shortest, so known states are never relaxed again
+            if !explored.index.contains_key(&next) {
+                queue.extend(explored.relax(next, cost, Some(id)));
+            }
+        }
+    }
+    (explored, None)
+}
+
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, usize>>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let (explored, goal) = run_bfs(starts, successors, is_goal);
+    goal.map(|goal| SearchResult {
+        cost: explored.best[goal].0,
+        goal,
+        explored,
+    })
+}
+
+// number of steps to every state reachable from the starts
+pub fn bfs_all<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    run_bfs(starts, successors, |_| false).0.into_distances()
+}
+
+fn run_astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Explored<S, C>, Option<usize>)
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut explored = Explored::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(id) = explored.relax(start, C::zero(), None) {
+            queue.push(Reverse((estimate, C::zero(), id)));
+        }
+    }
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        // a cheaper way to this state was queued after this one
+        if cost > explored.best[id].0 {
+            continue;
+        }
+        let state = explored.states[id].clone();
+        if is_goal(&state) {
+            return (explored, Some(id));
+        }
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            let estimate = next_cost + heuristic(&next);
+            if let Some(next_id) = explored.relax(next, next_cost, Some(id)) {
+                queue.push(Reverse((estimate, next_cost, next_id)));
+            }
+        }
+    }
+    (explored, None)
+}
+
+// `heuristic` must never overestimate the remaining cost for the result to be the cheapest
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (explored, goal) = run_astar(starts, successors, heuristic, is_goal);
+    goal.map(|goal| SearchResult {
+        cost: explored.best[goal].0,
+        goal,
+        explored,
+    })
+}
+
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+// cheapest cost to every state reachable from the starts
+pub fn dijkstra_all<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+) -> HashMap<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    run_astar(starts, successors, |_| C::zero(), |_| false)
+        .0
+        .into_distances()
+}
+
+#[cfg(test)]
+const MAZE: &str = "\
+S.#.....
+.##.###.
+....#..E
+.####.#.
+......#.";
+
+#[cfg(test)]
+fn maze_successors(maze: &[&[u8]], (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+    [
+        (x + 1, y),
+        (x.wrapping_sub(1), y),
+        (x, y + 1),
+        (x, y.wrapping_sub(1)),
+    ]
+    .into_iter()
+    .filter(|(x, y)| {
+        maze.get(*y)
+            .and_then(|row| row.get(*x))
+            .is_some_and(|c| *c != b'#')
+    })
+    .collect()
+}
+
+#[test]
+fn test_bfs() {
+    let maze = MAZE.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let result = bfs([(0, 0)], |p| maze_successors(&maze, *p), |p| *p == (7, 2)).unwrap();
+    assert_eq!(result.cost, 13);
+    let path = result.path();
+    assert_eq!(path.len(), 14);
+    assert_eq!((path[0], *result.goal()), ((0, 0), (7, 2)));
+    assert!(path
+        .windows(2)
+        .all(|w| maze_successors(&maze, w[0]).contains(&w[1])));
+    assert!(bfs([(0, 0)], |p| maze_successors(&maze, *p), |p| *p == (2, 0)).is_none());
+    // the closest of several starts wins
+    let result = bfs(
+        [(0, 0), (7, 4)],
+        |p| maze_successors(&maze, *p),
+        |p| *p == (7, 2),
+    )
+    .unwrap();
+    assert_eq!((result.cost, result.path()[0]), (2, (7, 4)));
+    let distances = bfs_all([(0, 0)], |p| maze_successors(&maze, *p));
+    assert_eq!(distances[&(0, 0)], 0);
+    assert_eq!(distances[&(7, 2)], 13);
+    assert_eq!(distances[&(5, 4)], 9);
+    assert_eq!(distances[&(5, 2)], 11);
+    assert_eq!(distances.len(), 27);
+}
+
+#[cfg(test)]
+fn weighted_successors(node: &char) -> Vec<(char, u32)> {
+    match node {
+        'a' => vec![('b', 7), ('c', 9), ('f', 14)],
+        'b' => vec![('a', 7), ('c', 10), ('d', 15)],
+        'c' => vec![('a', 9), ('b', 10), ('d', 11), ('f', 2)],
+        'd' => vec![('b', 15), ('c', 11), ('e', 6)],
+        'e' => vec![('d', 6), ('f', 9)],
+        'f' => vec![('a', 14), ('c', 2), ('e', 9)],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn test_dijkstra() {
+    let result = dijkstra(['a'], weighted_successors, |n| *n == 'e').unwrap();
+    assert_eq!(result.cost, 20);
+    assert_eq!(result.path(), vec!['a', 'c', 'f', 'e']);
+    let distances = dijkstra_all(['a'], weighted_successors);
+    assert_eq!(distances.len(), 6);
+    assert_eq!(distances[&'d'], 20);
+    assert_eq!(distances[&'f'], 11);
+    let result = dijkstra(['b', 'e'], weighted_successors, |n| *n == 'a').unwrap();
+    assert_eq!((result.cost, result.path()[0]), (7, 'b'));
+    assert!(dijkstra(['a'], weighted_successors, |n| *n == 'z').is_none());
+}
+
+#[test]
+fn test_astar() {
+    let maze = MAZE.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let goal = (7, 2);
+    let successors = |p: &(usize, usize)| maze_successors(&maze, *p).into_iter().map(|n| (n, 1));
+    let manhattan = |p: &(usize, usize)| p.0.abs_diff(goal.0) + p.1.abs_diff(goal.1);
+    let result = astar([(0, 0)], successors, manhattan, |p| *p == goal).unwrap();
+    let expected = dijkstra([(0, 0)], successors, |p| *p == goal).unwrap();
+    assert_eq!(result.cost, expected.cost);
+    assert_eq!(result.path().len(), expected.path().len());
+}
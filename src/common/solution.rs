@@ -1,6 +1,21 @@
-use std::time::Instant;
+use std::{env, time::Instant};
 
-use super::data::{get_examples, get_input, submit_answer};
+use super::{
+    data::{
+        dry_run_submission, examples_passed, get_examples, get_input, normalize_input,
+        record_examples_passed, submit_answer,
+    },
+    puzzle::PuzzleId,
+};
+
+// AOC_DRY_RUN=1 reports what would happen to an answer instead of posting it, AOC_DRY_RUN=0
+// posts it even if the examples never passed
+pub fn dry_run_override() -> Option<bool> {
+    env::var("AOC_DRY_RUN")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| value != "0")
+}
 
 pub trait AocSolution {
     const PART: u32;
@@ -23,9 +38,20 @@ pub trait AocSolution {
         (year, day)
     }
 
+    fn puzzle_id() -> PuzzleId {
+        let (year, day) = Self::year_day();
+        PuzzleId::new(year, day, Self::PART)
+            .unwrap_or_else(|err| panic!("{} is not a puzzle: {}", Self::solution_path(), err))
+    }
+
     fn implementation(input: &str) -> String;
 
     fn solve() {
+        Self::solve_with(dry_run_override())
+    }
+
+    // without an override, answers are only posted once all the examples have passed
+    fn solve_with(dry_run: Option<bool>) {
         let examples = Self::get_examples();
         for (i, (example, expected)) in examples.iter().enumerate() {
             let start = Instant::now();
@@ -44,14 +70,25 @@ pub trait AocSolution {
                 )
             }
         }
-        let (year, day) = Self::year_day();
-        let input = get_input(year, day).unwrap();
+        if !examples.is_empty() {
+            record_examples_passed(Self::puzzle_id()).unwrap();
+        }
+        let input = get_input(Self::puzzle_id()).unwrap();
+        let input = if Self::normalize_input() {
+            normalize_input(&input)
+        } else {
+            input
+        };
         let start = Instant::now();
         let answer = Self::implementation(&input);
         let elapsed = start.elapsed();
         println!("Answer `{}`; Solution ran in {:?}", answer, elapsed);
         if Self::do_post_answer() {
-            submit_answer(year, day, Self::PART, &answer).unwrap();
+            if dry_run.unwrap_or_else(Self::dry_run) {
+                dry_run_submission(Self::puzzle_id(), &answer).unwrap();
+            } else {
+                submit_answer(Self::puzzle_id(), &answer).unwrap();
+            }
         }
     }
 
@@ -59,9 +96,16 @@ pub trait AocSolution {
         true
     }
 
+    fn dry_run() -> bool {
+        !examples_passed(Self::puzzle_id())
+    }
+
+    fn normalize_input() -> bool {
+        false
+    }
+
     fn ydp() -> String {
-        let (year, day) = Self::year_day();
-        format!("y{}d{}p{}", year, day, Self::PART)
+        Self::puzzle_id().to_string()
     }
 
     fn map_example_input(example: &str) -> String {
@@ -69,8 +113,7 @@ pub trait AocSolution {
     }
 
     fn get_examples() -> Vec<(String, String)> {
-        let (year, day) = Self::year_day();
-        get_examples(year, day, Self::PART)
+        get_examples(Self::puzzle_id())
             .unwrap()
             .iter()
             .map(|(example, expected)| -> (String, String) {
@@ -79,3 +122,45 @@ pub trait AocSolution {
             .collect()
     }
 }
+
+#[cfg(test)]
+struct LastDayPart2 {}
+
+#[cfg(test)]
+impl AocSolution for LastDayPart2 {
+    const PART: u32 = 2;
+    fn solution_path() -> String {
+        "rust_advent::y2023::d25".to_string()
+    }
+
+    fn implementation(input: &str) -> String {
+        input.to_string()
+    }
+}
+
+#[test]
+#[should_panic(expected = "rust_advent::y2023::d25 is not a puzzle")]
+fn test_puzzle_id_names_solution() {
+    LastDayPart2::puzzle_id();
+}
+
+#[cfg(test)]
+struct NotYetUnlocked {}
+
+#[cfg(test)]
+impl AocSolution for NotYetUnlocked {
+    const PART: u32 = 1;
+    fn solution_path() -> String {
+        "rust_advent::y2999::d01".to_string()
+    }
+
+    fn implementation(input: &str) -> String {
+        input.to_string()
+    }
+}
+
+#[test]
+fn test_dry_run_without_examples_passed() {
+    assert!(!examples_passed(NotYetUnlocked::puzzle_id()));
+    assert!(NotYetUnlocked::dry_run());
+}
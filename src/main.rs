@@ -1,4 +1,9 @@
-use common::solution::AocSolution;
+use anyhow::{Context, Result};
+use common::{
+    data::{get_input, get_private_leaderboard},
+    puzzle::{latest_event_year, PuzzleId},
+    solution::{dry_run_override, AocSolution},
+};
 
 mod common;
 mod template;
@@ -6,6 +11,50 @@ mod y2018;
 mod y2022;
 mod y2023;
 
-fn main() {
-    y2018::d01::Part1::solve()
+fn main() -> Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // --dry-run can go anywhere, it only matters when solving
+    let dry_run = if args.iter().any(|arg| arg == "--dry-run") {
+        Some(true)
+    } else {
+        dry_run_override()
+    };
+    args.retain(|arg| arg != "--dry-run");
+    match args.first().map(String::as_str) {
+        Some("leaderboard") => {
+            let id = args.get(1).context("Usage: leaderboard <id> [year]")?;
+            let year = match args.get(2) {
+                Some(year) => year
+                    .parse()
+                    .with_context(|| format!("Invalid year `{}`", year))?,
+                None => latest_event_year(),
+            };
+            print!("{}", get_private_leaderboard(year, id)?);
+        }
+        Some("--dot") => {
+            let usage = "Usage: --dot <file> <year> <day>";
+            let (file, year, day) = match &args[1..] {
+                [file, year, day] => (file, year, day),
+                _ => anyhow::bail!(usage),
+            };
+            let year: u32 = year
+                .parse()
+                .with_context(|| format!("Invalid year `{}`", year))?;
+            let day: u32 = day
+                .parse()
+                .with_context(|| format!("Invalid day `{}`", day))?;
+            // no point fetching an input nothing can export
+            let exporter = match year {
+                2022 => y2022::dot_exporter(day),
+                2023 => y2023::dot_exporter(day),
+                _ => None,
+            }
+            .with_context(|| format!("No graph export for {} day {}", year, day))?;
+            let dot = exporter(&get_input(PuzzleId::new(year, day, 1)?)?);
+            std::fs::write(file, dot.to_string())
+                .with_context(|| format!("Failed to write `{}`", file))?;
+        }
+        _ => y2018::d01::Part1::solve_with(dry_run),
+    }
+    Ok(())
 }
@@ -1,13 +1,11 @@
-use std::ops::RangeInclusive;
-
 use regex::Regex;
 
-use crate::common::solution::AocSolution;
+use crate::common::{interval::Interval, solution::AocSolution};
 
 struct Part1 {}
 struct Part2 {}
 
-fn parse_ranges(input: &str) -> Vec<(u32, u32, u32, u32)> {
+fn parse_ranges(input: &str) -> Vec<(Interval<u32>, Interval<u32>)> {
     let mut ranges = Vec::new();
     let re = Regex::new(r"(\d+)-(\d+),(\d+)-(\d+)").unwrap();
     for matches in re.captures_iter(input) {
@@ -15,23 +13,14 @@ fn parse_ranges(input: &str) -> Vec<(u32, u32, u32, u32)> {
         let b: u32 = matches[2].parse().unwrap();
         let c: u32 = matches[3].parse().unwrap();
         let d: u32 = matches[4].parse().unwrap();
-        ranges.push((a, b, c, d));
+        ranges.push((
+            Interval::from_inclusive(a, b),
+            Interval::from_inclusive(c, d),
+        ));
     }
     ranges
 }
 
-fn range_fully_contains(outer: &RangeInclusive<&u32>, inner_start: &u32, inner_end: &u32) -> bool {
-    outer.contains(&inner_start) && outer.contains(&inner_end)
-}
-
-fn range_partially_contains(
-    outer: &RangeInclusive<&u32>,
-    inner_start: &u32,
-    inner_end: &u32,
-) -> bool {
-    outer.contains(&inner_start) || outer.contains(&inner_end)
-}
-
 impl AocSolution for Part1 {
     const PART: u32 = 1;
     fn solution_path() -> String {
@@ -41,9 +30,7 @@ impl AocSolution for Part1 {
     fn implementation(input: &str) -> String {
         parse_ranges(input)
             .iter()
-            .filter(|(a, b, c, d)| {
-                range_fully_contains(&(a..=b), c, d) || range_fully_contains(&(c..=d), a, b)
-            })
+            .filter(|(a, b)| a.contains_interval(b) || b.contains_interval(a))
             .count()
             .to_string()
     }
@@ -58,9 +45,7 @@ impl AocSolution for Part2 {
     fn implementation(input: &str) -> String {
         parse_ranges(input)
             .iter()
-            .filter(|(a, b, c, d)| {
-                range_partially_contains(&(a..=b), c, d) || range_partially_contains(&(c..=d), a, b)
-            })
+            .filter(|(a, b)| a.overlaps(b))
             .count()
             .to_string()
     }
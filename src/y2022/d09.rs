@@ -1,51 +1,42 @@
-use std::collections::HashSet;
-
-use crate::common::solution::AocSolution;
+use crate::common::{
+    grid::{Direction, Point2, SparseGrid},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
 
-fn move_tail((hx, hy): (i32, i32), (tx, ty): (i32, i32)) -> (i32, i32) {
-    let distance_x = hx - tx;
-    let distance_y = hy - ty;
-    let total_distance = distance_x.abs().max(distance_y.abs());
-    let dx = if total_distance.abs() > 1 {
-        distance_x.signum()
-    } else {
-        0
-    };
-    let dy = if total_distance.abs() > 1 {
-        distance_y.signum()
+type Knot = Point2<i64>;
+
+fn move_tail(head: Knot, tail: Knot) -> Knot {
+    let distance = head - tail;
+    if head.chebyshev_distance(&tail) > 1 {
+        tail + Point2::new(distance.x.signum(), distance.y.signum())
     } else {
-        0
-    };
-    (tx + dx, ty + dy)
+        tail
+    }
+}
+
+fn parse_command(command: &str) -> (Direction, usize) {
+    let (direction, amount) = command.split_once(' ').unwrap();
+    (direction.parse().unwrap(), amount.parse().unwrap())
 }
 
 fn move_head_command(
     command: &str,
-    visited: &mut HashSet<(i32, i32)>,
-    (hx, hy): (i32, i32),
-    tail_pos: (i32, i32),
-) -> ((i32, i32), (i32, i32)) {
-    let (direction, amount) = command.split_once(' ').unwrap();
-    let (dx, dy) = match direction {
-        "U" => (0, 1),
-        "D" => (0, -1),
-        "L" => (-1, 0),
-        "R" => (1, 0),
-        _ => unreachable!(),
-    };
-    let mut hx = hx;
-    let mut hy = hy;
-    let mut tail_pos = tail_pos;
-    for _ in 0..amount.parse().unwrap() {
-        hx += dx;
-        hy += dy;
-        tail_pos = move_tail((hx, hy), tail_pos);
-        visited.insert(tail_pos);
+    visited: &mut SparseGrid<()>,
+    head: Knot,
+    tail: Knot,
+) -> (Knot, Knot) {
+    let (direction, amount) = parse_command(command);
+    let mut head = head;
+    let mut tail = tail;
+    for _ in 0..amount {
+        head += direction.delta();
+        tail = move_tail(head, tail);
+        visited.insert(tail, ());
     }
-    ((hx, hy), tail_pos)
+    (head, tail)
 }
 
 impl AocSolution for Part1 {
@@ -55,10 +46,10 @@ impl AocSolution for Part1 {
     }
 
     fn implementation(input: &str) -> String {
-        let mut visited: HashSet<(i32, i32)> = HashSet::new();
-        let mut head_pos = (0, 0);
+        let mut visited = SparseGrid::new();
+        let mut head_pos = Point2::default();
         let mut tail_pos = head_pos;
-        visited.insert(tail_pos);
+        visited.insert(tail_pos, ());
         for line in input.lines() {
             (head_pos, tail_pos) = move_head_command(line, &mut visited, head_pos, tail_pos);
         }
@@ -66,22 +57,14 @@ impl AocSolution for Part1 {
     }
 }
 
-fn move_rope(command: &str, visited: &mut HashSet<(i32, i32)>, rope: &mut Vec<(i32, i32)>) {
-    let (direction, amount) = command.split_once(' ').unwrap();
-    let (dx, dy) = match direction {
-        "U" => (0, 1),
-        "D" => (0, -1),
-        "L" => (-1, 0),
-        "R" => (1, 0),
-        _ => unreachable!(),
-    };
-    for _ in 0..amount.parse().unwrap() {
-        rope[0].0 += dx;
-        rope[0].1 += dy;
+fn move_rope(command: &str, visited: &mut SparseGrid<()>, rope: &mut [Knot]) {
+    let (direction, amount) = parse_command(command);
+    for _ in 0..amount {
+        rope[0] += direction.delta();
         for i in 1..rope.len() {
             rope[i] = move_tail(rope[i - 1], rope[i]);
         }
-        visited.insert(*rope.last().unwrap());
+        visited.insert(*rope.last().unwrap(), ());
     }
 }
 
@@ -94,9 +77,9 @@ impl AocSolution for Part1Impl2 {
     }
 
     fn implementation(input: &str) -> String {
-        let mut visited: HashSet<(i32, i32)> = HashSet::new();
-        let mut rope = vec![(0, 0), (0, 0)];
-        visited.insert((0, 0));
+        let mut visited = SparseGrid::new();
+        let mut rope = vec![Point2::default(); 2];
+        visited.insert(Point2::default(), ());
         for line in input.lines() {
             move_rope(line, &mut visited, &mut rope);
         }
@@ -111,20 +94,9 @@ impl AocSolution for Part2 {
     }
 
     fn implementation(input: &str) -> String {
-        let mut visited: HashSet<(i32, i32)> = HashSet::new();
-        let mut rope = vec![
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-            (0, 0),
-        ];
-        visited.insert((0, 0));
+        let mut visited = SparseGrid::new();
+        let mut rope = vec![Point2::default(); 10];
+        visited.insert(Point2::default(), ());
         for line in input.lines() {
             move_rope(line, &mut visited, &mut rope);
         }
@@ -1,11 +1,15 @@
-use std::collections::{HashMap, HashSet, LinkedList};
+use std::collections::HashMap;
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    grid::{Direction, Point2, Vector2},
+    search::bfs,
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
 
-type Point2d = (i32, i32);
+type Point2d = Point2<i32>;
 
 struct HeightGrid {
     grid: HashMap<Point2d, u8>,
@@ -14,11 +18,17 @@ struct HeightGrid {
 }
 
 impl HeightGrid {
-    fn neighbors(&self, (px, py): Point2d) -> Vec<Point2d> {
+    fn neighbors(&self, p: Point2d) -> Vec<Point2d> {
         let mut result = vec![];
-        if let Some(p_height) = self.grid.get(&(px, py)) {
-            for (dx, dy) in &[(1, 0), (-1, 0), (0, 1), (0, -1)] {
-                let new_p = (px + dx, py + dy);
+        if let Some(p_height) = self.grid.get(&p) {
+            for direction in [
+                Direction::Right,
+                Direction::Left,
+                Direction::Down,
+                Direction::Up,
+            ] {
+                let step: Vector2<i32> = direction.into();
+                let new_p = p + step;
                 if let Some(height) = self.grid.get(&new_p) {
                     if *height <= p_height + 1 {
                         result.push(new_p);
@@ -29,48 +39,33 @@ impl HeightGrid {
         result
     }
 
-    fn path_length_start_to_end(&self) -> u32 {
-        self.path_length(self.start)
+    fn path_length_start_to_end(&self) -> usize {
+        self.path_length([self.start])
     }
 
-    fn path_length(&self, start: Point2d) -> u32 {
-        let mut queue = LinkedList::new();
-        queue.push_back((start, 0));
-        let mut visited: HashSet<Point2d> = HashSet::new();
-        visited.insert(start);
-        while let Some((p, len)) = queue.pop_front() {
-            let neighbors = self.neighbors(p);
-            let new_len = len + 1;
-            for n in neighbors {
-                if n == self.end {
-                    return new_len;
-                } else if !visited.contains(&n) {
-                    visited.insert(n);
-                    queue.push_back((n, new_len));
-                }
-            }
-        }
-        u32::MAX
+    // the shortest climb to the end from any of the starts
+    fn path_length(&self, starts: impl IntoIterator<Item = Point2d>) -> usize {
+        bfs(starts, |p| self.neighbors(*p), |p| *p == self.end).map_or(usize::MAX, |path| path.cost)
     }
 }
 
 fn parse_input(input: &str) -> HeightGrid {
     let mut grid = HashMap::new();
-    let mut start = (0, 0);
-    let mut end = (0, 0);
+    let mut start = Point2::default();
+    let mut end = Point2::default();
     for (y, line) in input.lines().enumerate() {
         let y = y as i32;
         for (x, c) in line.as_bytes().iter().enumerate() {
             let x = x as i32;
             grid.insert(
-                (x, y),
+                Point2::new(x, y),
                 match c {
                     b'S' => {
-                        start = (x, y);
+                        start = Point2::new(x, y);
                         b'a'
                     }
                     b'E' => {
-                        end = (x, y);
+                        end = Point2::new(x, y);
                         b'z'
                     }
                     _ => *c,
@@ -101,13 +96,12 @@ impl AocSolution for Part2 {
 
     fn implementation(input: &str) -> String {
         let grid = parse_input(input);
-        (0..42)
-            .filter_map(|i| grid.grid.get(&(0, i)).map(|h| ((0, i), h)))
-            .filter(|(_, h)| **h == 0)
-            .map(|(p, _)| grid.path_length(p))
-            .min()
-            .unwrap()
-            .to_string()
+        let lowest = grid
+            .grid
+            .iter()
+            .filter(|(_, height)| **height == 0)
+            .map(|(p, _)| *p);
+        grid.path_length(lowest).to_string()
     }
 }
 
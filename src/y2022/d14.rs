@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
 
 use itertools::Itertools;
 use nom::{
@@ -6,13 +6,18 @@ use nom::{
     sequence::separated_pair, IResult,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    grid::{
+        render::{Color, Renderer},
+        Point2, SparseGrid,
+    },
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Point2d(i64, i64);
+type Point2d = Point2<i64>;
 
 #[derive(Debug, PartialEq)]
 enum Block {
@@ -20,14 +25,19 @@ enum Block {
     Rock,
 }
 
+impl Block {
+    fn glyph(&self) -> char {
+        match self {
+            Block::Rock => '#',
+            Block::Sand => 'o',
+        }
+    }
+}
+
 #[derive(Debug)]
 struct RockGrid {
-    grid: HashMap<Point2d, Block>,
+    grid: SparseGrid<Block>,
     sand_source_column: i64,
-    top_boundary: i64,
-    bottom_boundary: i64,
-    left_boundary: i64,
-    right_boundary: i64,
 }
 
 impl Default for RockGrid {
@@ -36,50 +46,41 @@ impl Default for RockGrid {
         Self {
             grid: Default::default(),
             sand_source_column: sand_column,
-            top_boundary: Default::default(),
-            bottom_boundary: Default::default(),
-            left_boundary: Default::default(),
-            right_boundary: Default::default(),
         }
     }
 }
 
 impl Display for RockGrid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut lines = vec![];
-        for y in (self.top_boundary - 1)..=(self.bottom_boundary + 1) {
-            let line: String = ((self.left_boundary - 1)..=(self.right_boundary + 1))
-                .map(|x| match self.grid.get(&Point2d(x, y)) {
-                    Some(Block::Rock) => '#',
-                    Some(Block::Sand) => 'o',
-                    None => '.',
-                })
-                .collect();
-            lines.push(line);
-        }
-        write!(f, "{}", lines.join("\n"))
+        let source = Point2::new(self.sand_source_column, 0);
+        let (min, max) = self.grid.bounds().unwrap_or((source, source));
+        let origin = Point2::new(min.x - 1, min.y.min(0) - 1);
+        let size = Point2::new(max.x + 2, max.y + 2) - origin;
+        let cursor = source - origin;
+        let renderer = Renderer::new(size.x as usize, size.y as usize, |x, y| {
+            self.grid
+                .get(origin + Point2::new(x as i64, y as i64))
+                .map_or('.', Block::glyph)
+        })
+        .cursor((cursor.x as usize, cursor.y as usize), '+', Color::Yellow);
+        write!(f, "{}", renderer)
     }
 }
 
 impl RockGrid {
+    // sand never comes to rest below the lowest rock, so this is also the lowest rock
+    fn bottom_boundary(&self) -> i64 {
+        self.grid.bounds().map_or(0, |(_, max)| max.y)
+    }
+
     fn add_rock_path(&mut self, path: Vec<Point2d>) {
-        if self.grid.is_empty() && !path.is_empty() {
-            self.left_boundary = path[0].0;
-            self.right_boundary = path[0].0;
-            self.top_boundary = 0;
-            self.bottom_boundary = path[0].1;
-        }
         for (p1, p2) in path.iter().tuple_windows() {
-            let first_x = p1.0.min(p2.0);
-            let second_x = p1.0.max(p2.0);
-            let first_y = p1.1.min(p2.1);
-            let second_y = p1.1.max(p2.1);
+            let first_x = p1.x.min(p2.x);
+            let second_x = p1.x.max(p2.x);
+            let first_y = p1.y.min(p2.y);
+            let second_y = p1.y.max(p2.y);
             for (x, y) in (first_x..=second_x).cartesian_product(first_y..=second_y) {
-                self.grid.insert(Point2d(x, y), Block::Rock);
-                self.left_boundary = self.left_boundary.min(x);
-                self.right_boundary = self.right_boundary.max(x);
-                self.top_boundary = self.top_boundary.min(y);
-                self.bottom_boundary = self.bottom_boundary.max(y);
+                self.grid.insert(Point2::new(x, y), Block::Rock);
             }
         }
     }
@@ -90,15 +91,15 @@ impl RockGrid {
 
     // returns true if a solid block is in pos (x,y) when returning
     fn drop_sands_recursive(&mut self, p: Point2d) -> bool {
-        let Point2d(x, y) = p;
-        if y > self.bottom_boundary {
+        let Point2 { x, y } = p;
+        if y > self.bottom_boundary() {
             return false;
-        } else if self.grid.contains_key(&p) {
+        } else if self.grid.contains(p) {
             return true;
         }
-        if self.drop_sands_recursive(Point2d(x, y + 1))
-            && self.drop_sands_recursive(Point2d(x - 1, y + 1))
-            && self.drop_sands_recursive(Point2d(x + 1, y + 1))
+        if self.drop_sands_recursive(Point2::new(x, y + 1))
+            && self.drop_sands_recursive(Point2::new(x - 1, y + 1))
+            && self.drop_sands_recursive(Point2::new(x + 1, y + 1))
         {
             self.grid.insert(p, Block::Sand);
             true
@@ -108,7 +109,7 @@ impl RockGrid {
     }
 
     fn drop_sands(&mut self) {
-        self.drop_sands_recursive(Point2d(self.sand_source_column, 0));
+        self.drop_sands_recursive(Point2::new(self.sand_source_column, 0));
     }
 }
 
@@ -117,14 +118,18 @@ fn test_add_rock_path() {
     let mut grid = RockGrid::default();
     println!("{:?}", grid);
     println!("{}\n", grid);
-    grid.add_rock_path(vec![Point2d(498, 4), Point2d(498, 6), Point2d(496, 6)]);
+    grid.add_rock_path(vec![
+        Point2::new(498, 4),
+        Point2::new(498, 6),
+        Point2::new(496, 6),
+    ]);
     println!("{:?}", grid);
     println!("{}\n", grid);
     grid.add_rock_path(vec![
-        Point2d(503, 4),
-        Point2d(502, 4),
-        Point2d(502, 9),
-        Point2d(494, 9),
+        Point2::new(503, 4),
+        Point2::new(502, 4),
+        Point2::new(502, 9),
+        Point2::new(494, 9),
     ]);
     println!("{:?}", grid);
     println!("{}\n", grid);
@@ -139,7 +144,7 @@ fn parse_rock_position(input: &str) -> IResult<&str, Point2d> {
             tag(","),
             nom::character::complete::i64,
         ),
-        |(x, y)| Point2d(x, y),
+        |(x, y)| Point2::new(x, y),
     )(input)
 }
 
@@ -181,15 +186,10 @@ impl AocSolution for Part2 {
 
     fn implementation(input: &str) -> String {
         let mut rock_grid = parse_input(input);
+        let (min, max) = rock_grid.grid.bounds().unwrap();
         rock_grid.add_rock_path(vec![
-            Point2d(
-                rock_grid.left_boundary - rock_grid.bottom_boundary - 2,
-                rock_grid.bottom_boundary + 2,
-            ),
-            Point2d(
-                rock_grid.right_boundary + rock_grid.bottom_boundary + 2,
-                rock_grid.bottom_boundary + 2,
-            ),
+            Point2::new(min.x - max.y - 2, max.y + 2),
+            Point2::new(max.x + max.y + 2, max.y + 2),
         ]);
         rock_grid.drop_sands();
         rock_grid.count_sand().to_string()
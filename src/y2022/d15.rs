@@ -8,39 +8,34 @@ use nom::{
     IResult,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    grid::Point2,
+    interval::{Interval, IntervalSet},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
 
-#[derive(Debug)]
-struct Point2d(i32, i32);
-
-impl Point2d {
-    fn distance_manhattan(&self, other: &Point2d) -> u32 {
-        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
-    }
-}
-
 #[derive(Debug)]
 struct Sensor {
-    position: Point2d,
-    nearest_beacon: Point2d,
+    position: Point2<i32>,
+    nearest_beacon: Point2<i32>,
 }
 
 impl Sensor {
     fn beacon_distance(&self) -> u32 {
-        self.position.distance_manhattan(&self.nearest_beacon)
+        self.position.manhattan_distance(&self.nearest_beacon) as u32
     }
 
     fn covered_points_in_row(&self, row: i32) -> Option<(i32, i32)> {
-        let row_distance = row.abs_diff(self.position.1);
+        let row_distance = row.abs_diff(self.position.y);
         let beacon_distance = self.beacon_distance();
         if row_distance > beacon_distance {
             return None;
         }
         let row_width = (beacon_distance - row_distance) as i32;
-        Some((self.position.0 - row_width, self.position.0 + row_width))
+        Some((self.position.x - row_width, self.position.x + row_width))
     }
 }
 
@@ -48,65 +43,60 @@ impl Sensor {
 fn test_sensor_covered_points_in_row() {
     assert_eq!(
         Sensor {
-            position: Point2d(0, 0),
-            nearest_beacon: Point2d(1, 0)
+            position: Point2::new(0, 0),
+            nearest_beacon: Point2::new(1, 0)
         }
         .covered_points_in_row(1),
         Some((0, 0))
     );
     assert_eq!(
         Sensor {
-            position: Point2d(8, 7),
-            nearest_beacon: Point2d(2, 10)
+            position: Point2::new(8, 7),
+            nearest_beacon: Point2::new(2, 10)
         }
         .covered_points_in_row(1),
         Some((5, 11))
     );
     assert_eq!(
         Sensor {
-            position: Point2d(0, 11),
-            nearest_beacon: Point2d(2, 10)
+            position: Point2::new(0, 11),
+            nearest_beacon: Point2::new(2, 10)
         }
         .covered_points_in_row(13),
         Some((-2, 2))
     );
 }
 
-fn combine_overlapping_ranges(ranges: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    if ranges.is_empty() {
-        return vec![];
-    }
-    let mut result = vec![];
-    let mut ranges = ranges.iter().sorted_by(|(a, _), (b, _)| Ord::cmp(a, b));
-    let current_range = ranges.next().unwrap();
-    let mut current_range = (current_range.0, current_range.1);
-    for range in ranges {
-        if current_range.1 < (range.0 - 1) {
-            result.push(current_range);
-            current_range = (range.0, range.1);
-        } else {
-            current_range.1 = current_range.1.max(range.1);
-        }
-    }
-    result.push(current_range);
-    result
+// the ranges are inclusive, and ranges that only touch are combined too
+fn covered_set(ranges: &[(i32, i32)]) -> IntervalSet<i32> {
+    ranges
+        .iter()
+        .map(|(start, end)| Interval::from_inclusive(*start, *end))
+        .collect()
+}
+
+#[cfg(test)]
+fn covered_ranges(ranges: &[(i32, i32)]) -> Vec<(i32, i32)> {
+    covered_set(ranges)
+        .intervals()
+        .iter()
+        .map(|i| (i.start, i.end - 1))
+        .collect()
 }
 
 #[test]
-fn test_combine_overlapping_ranges() {
-    assert_eq!(combine_overlapping_ranges(&[]), vec![]);
-    assert_eq!(combine_overlapping_ranges(&[(0, 8)]), vec![(0, 8)]);
+fn test_covered_set() {
+    assert_eq!(covered_ranges(&[]), vec![]);
+    assert_eq!(covered_ranges(&[(0, 8)]), vec![(0, 8)]);
+    assert_eq!(covered_ranges(&[(0, 8), (8, 10)]), vec![(0, 10)]);
+    assert_eq!(covered_ranges(&[(0, 7), (8, 10)]), vec![(0, 10)]);
     assert_eq!(
-        combine_overlapping_ranges(&[(0, 8), (8, 10)]),
-        vec![(0, 10)]
-    );
-    assert_eq!(
-        combine_overlapping_ranges(&[(0, 7), (8, 10)]),
-        vec![(0, 10)]
+        covered_ranges(&[(8, 11), (6, 14), (3, 4), (8, 10), (0, 2), (10, 15)]),
+        vec![(0, 4), (6, 15)]
     );
     assert_eq!(
-        combine_overlapping_ranges(&[(8, 11), (6, 14), (3, 4), (8, 10), (0, 2), (10, 15)]),
-        vec![(0, 4), (6, 15)]
+        covered_set(&[(8, 11), (6, 14), (3, 4), (8, 10), (0, 2), (10, 15)]).total_len(),
+        15
     );
 }
 
@@ -123,8 +113,8 @@ fn parse_line(line: &str) -> IResult<&str, Sensor> {
             complete::i32,
         )),
         |(_, sx, _, sy, _, bx, _, by)| Sensor {
-            position: Point2d(sx, sy),
-            nearest_beacon: Point2d(bx, by),
+            position: Point2::new(sx, sy),
+            nearest_beacon: Point2::new(bx, by),
         },
     )(line)
 }
@@ -155,8 +145,8 @@ impl AocSolution for Part1 {
         let beacon_columns = sensors
             .iter()
             .filter_map(|s| {
-                if s.nearest_beacon.1 == row {
-                    Some(s.nearest_beacon.0)
+                if s.nearest_beacon.y == row {
+                    Some(s.nearest_beacon.x)
                 } else {
                     None
                 }
@@ -167,13 +157,12 @@ impl AocSolution for Part1 {
             .iter()
             .filter_map(|s| s.covered_points_in_row(row))
             .collect_vec();
-        let combined_ranges = combine_overlapping_ranges(&ranges);
+        let covered = covered_set(&ranges);
         let beacons_within_combined_ranges = beacon_columns
             .iter()
-            .filter(|b| combined_ranges.iter().any(|(r0, r1)| r0 <= b && *b <= r1))
+            .filter(|b| covered.contains(b))
             .count() as i32;
-        let range_count = combined_ranges.iter().map(|(a, b)| b - a + 1).sum::<i32>();
-        (range_count - beacons_within_combined_ranges).to_string()
+        (covered.total_len() - beacons_within_combined_ranges).to_string()
     }
 }
 
@@ -200,9 +189,9 @@ impl AocSolution for Part2 {
                 .iter()
                 .filter_map(|s| s.covered_points_in_row(row))
                 .collect_vec();
-            let combined_ranges = combine_overlapping_ranges(&ranges);
-            if combined_ranges.len() > 1 {
-                let col = (combined_ranges.first().unwrap().1 + 1) as u64;
+            let covered = covered_set(&ranges);
+            if covered.intervals().len() > 1 {
+                let col = covered.intervals()[0].end as u64;
                 return (col * 4000000 + row as u64).to_string();
             }
         }
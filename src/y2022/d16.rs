@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet, LinkedList};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 use nom::{
@@ -11,7 +11,14 @@ use nom::{
     IResult,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    bnb::{branch_and_bound, BnbResult, Strategy},
+    graph::{
+        dot::{Dot, Shape},
+        Graph,
+    },
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -57,6 +64,23 @@ fn parse_line(line: &str) -> IResult<&str, (&str, ValveCave)> {
     )(line)
 }
 
+// tunnels go both ways, so each one is a single undirected edge
+pub fn dot(input: &str) -> Dot {
+    let cave_map = parse_input(input);
+    let mut dot = Dot::graph("valves");
+    for (name, valve) in cave_map.iter().sorted_by_key(|(name, _)| **name) {
+        let label = format!("{}\n{}", name, valve.flow_rate);
+        let shape = (valve.flow_rate > 0).then_some(Shape::Box);
+        dot.add_node(name, Some(&label), shape);
+    }
+    for (name, valve) in cave_map.iter().sorted_by_key(|(name, _)| **name) {
+        for neighbor in valve.neighbors.iter().filter(|n| name < *n) {
+            dot.add_edge(name, neighbor, None);
+        }
+    }
+    dot
+}
+
 #[derive(Debug)]
 struct CavePath<'a> {
     name: &'a str,
@@ -78,33 +102,25 @@ fn cave_paths<'a>(
         })
         .collect_vec();
 
+    let mut graph = Graph::new();
+    for (name, valve) in cave_map {
+        graph.add_edges(name, valve.neighbors.iter().copied());
+    }
+    let distances = graph.bfs_all_pairs();
+
     let mut all_paths = HashMap::new();
     for cave_name in usable_valves {
-        let mut distances_to = vec![];
-        let mut visited = HashSet::new();
-        let mut queue = LinkedList::new();
-        queue.push_back((*cave_name, 0u32));
-        visited.insert(*cave_name);
-        while let Some((next, dist)) = queue.pop_front() {
-            let valve = cave_map.get(next).unwrap();
-            if valve.flow_rate > 0 {
-                distances_to.push((next, dist + 1, valve));
-            }
-            for n in &valve.neighbors {
-                if !visited.contains(n) {
-                    queue.push_back((n, dist + 1));
-                    visited.insert(n);
-                }
-            }
-        }
-        let mut paths = vec![];
-        for (n, d, v) in distances_to {
-            paths.push(CavePath {
-                name: n,
-                time: d,
-                flow_rate: v.flow_rate,
-            });
-        }
+        let paths = distances[graph.id(cave_name).unwrap()]
+            .iter()
+            .enumerate()
+            .filter_map(|(id, distance)| Some((graph.name(id), (*distance)?)))
+            .filter(|(name, _)| cave_map[name].flow_rate > 0)
+            .map(|(name, distance)| CavePath {
+                name,
+                time: distance as u32 + 1,
+                flow_rate: cave_map[name].flow_rate,
+            })
+            .collect_vec();
         all_paths.insert(*cave_name, paths);
     }
 
@@ -120,31 +136,86 @@ fn test_cave_paths() {
     println!("{:?}", paths);
 }
 
-fn most_pressure_released<'a>(
-    current_location: &'a str,
-    cave_paths: &HashMap<&'a str, Vec<CavePath<'a>>>,
-    minutes_elapsed: u32,
-    pressure_so_far: u32,
-    visited: &mut Vec<&'a str>,
-) -> u32 {
-    let mut max_so_far = pressure_so_far;
-    for CavePath {
-        name,
-        time,
-        flow_rate,
-    } in cave_paths.get(&current_location).unwrap()
-    {
-        let new_time = minutes_elapsed + time;
-        if new_time < 30 && !visited.contains(name) {
-            visited.push(name);
-            let new_pressure = pressure_so_far + (flow_rate * (30 - new_time));
-            let pressure_released =
-                most_pressure_released(name, cave_paths, new_time, new_pressure, visited);
-            visited.pop();
-            max_so_far = max_so_far.max(pressure_released);
+// where everyone is and the minute they're done there, with every valve opened so far
+#[derive(Debug, Clone)]
+struct ValveState<'a> {
+    actors: Vec<(&'a str, u32)>,
+    released: u32,
+    opened: Vec<&'a str>,
+}
+
+impl<'a> ValveState<'a> {
+    fn start(actors: usize) -> Self {
+        ValveState {
+            actors: vec![("AA", 0); actors],
+            released: 0,
+            opened: vec![],
+        }
+    }
+
+    // whoever is free first goes to open another valve, or stops if none can be reached in time
+    fn next_steps(
+        &self,
+        cave_paths: &HashMap<&'a str, Vec<CavePath<'a>>>,
+        minutes: u32,
+    ) -> Vec<ValveState<'a>> {
+        let Some((actor, &(location, elapsed))) =
+            self.actors.iter().enumerate().min_by_key(|(_, (_, t))| *t)
+        else {
+            return vec![];
+        };
+        let steps = cave_paths[location]
+            .iter()
+            .filter(|path| elapsed + path.time < minutes && !self.opened.contains(&path.name))
+            .map(|path| {
+                let new_time = elapsed + path.time;
+                let mut next = self.clone();
+                next.actors[actor] = (path.name, new_time);
+                next.released += path.flow_rate * (minutes - new_time);
+                next.opened.push(path.name);
+                next
+            })
+            .collect_vec();
+        if !steps.is_empty() {
+            return steps;
+        }
+        let mut next = self.clone();
+        next.actors.remove(actor);
+        vec![next]
+    }
+
+    // as if every valve still shut gets opened by whoever could get to it first
+    fn max_possible_release(
+        &self,
+        cave_paths: &HashMap<&'a str, Vec<CavePath<'a>>>,
+        minutes: u32,
+    ) -> u32 {
+        let mut best = HashMap::new();
+        for (location, elapsed) in &self.actors {
+            for path in &cave_paths[location] {
+                if elapsed + path.time < minutes && !self.opened.contains(&path.name) {
+                    let release = path.flow_rate * (minutes - elapsed - path.time);
+                    let entry = best.entry(path.name).or_insert(0);
+                    *entry = release.max(*entry);
+                }
+            }
         }
+        self.released + best.values().sum::<u32>()
     }
-    max_so_far
+}
+
+fn most_pressure_released<'a>(
+    cave_paths: &HashMap<&'a str, Vec<CavePath<'a>>>,
+    actors: usize,
+    minutes: u32,
+) -> BnbResult<ValveState<'a>, u32> {
+    branch_and_bound(
+        ValveState::start(actors),
+        Strategy::DepthFirst,
+        |state| state.next_steps(cave_paths, minutes),
+        |state| state.released,
+        |state| state.max_possible_release(cave_paths, minutes),
+    )
 }
 
 impl AocSolution for Part1 {
@@ -156,50 +227,8 @@ impl AocSolution for Part1 {
     fn implementation(input: &str) -> String {
         let cave_map = parse_input(input);
         let paths = cave_paths(&cave_map);
-        let mut visited = vec![];
-        most_pressure_released("AA", &paths, 0, 0, &mut visited).to_string()
-    }
-}
-
-fn most_pressure_released_p2<'a>(
-    location1: &'a str,
-    minutes1: u32,
-    location2: &'a str,
-    minutes2: u32,
-    pressure_so_far: u32,
-    cave_paths: &HashMap<&'a str, Vec<CavePath<'a>>>,
-    visited: &mut Vec<&'a str>,
-) -> u32 {
-    let mut max_so_far = pressure_so_far;
-    for CavePath {
-        name,
-        time,
-        flow_rate,
-    } in cave_paths.get(&location1).unwrap()
-    {
-        let new_time = minutes1 + time;
-        if new_time < 26 && !visited.contains(name) {
-            visited.push(name);
-            let new_pressure = pressure_so_far + (flow_rate * (26 - new_time));
-            let (location1, minutes1, location2, minutes2) = if new_time < minutes2 {
-                (*name, new_time, location2, minutes2)
-            } else {
-                (location2, minutes2, *name, new_time)
-            };
-            let pressure_released = most_pressure_released_p2(
-                location1,
-                minutes1,
-                location2,
-                minutes2,
-                new_pressure,
-                cave_paths,
-                visited,
-            );
-            visited.pop();
-            max_so_far = max_so_far.max(pressure_released);
-        }
+        most_pressure_released(&paths, 1, 30).value.to_string()
     }
-    max_so_far
 }
 
 impl AocSolution for Part2 {
@@ -211,11 +240,39 @@ impl AocSolution for Part2 {
     fn implementation(input: &str) -> String {
         let cave_map = parse_input(input);
         let paths = cave_paths(&cave_map);
-        let mut visited = vec![];
-        most_pressure_released_p2("AA", 0, "AA", 0, 0, &paths, &mut visited).to_string()
+        most_pressure_released(&paths, 2, 26).value.to_string()
     }
 }
 
+#[cfg(test)]
+const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+#[test]
+fn test_dot() {
+    assert_eq!(dot(EXAMPLE).to_string(), include_str!("golden/d16.dot"));
+}
+
+#[test]
+fn test_most_pressure_released() {
+    let cave_map = parse_input(EXAMPLE);
+    let paths = cave_paths(&cave_map);
+    let alone = most_pressure_released(&paths, 1, 30);
+    assert_eq!(alone.value, 1651);
+    assert!(alone.pruned > 0);
+    let with_elephant = most_pressure_released(&paths, 2, 26);
+    assert_eq!(with_elephant.value, 1707);
+    assert_eq!(with_elephant.state.opened.len(), 6);
+}
+
 #[test]
 fn p1_pull_examples() {
     Part1::get_examples();
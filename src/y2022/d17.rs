@@ -1,8 +1,12 @@
-use std::{collections::HashMap, fmt::Display, ops::Shr};
+use std::fmt::Display;
 
 use itertools::Itertools;
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    cycle::find_cycle,
+    grid::{render::Renderer, BitGrid},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -25,22 +29,28 @@ fn parse_input(input: &str) -> Vec<JetDirection> {
         .collect_vec()
 }
 
+const CHAMBER_WIDTH: usize = 7;
+
 // Each rock appears so that its left edge is two units away from the left wall
 // and its bottom edge is three units above the highest rock in the room
 // (or the floor, if there isn't one).
-fn blocks() -> Vec<Vec<u16>> {
-    vec![
-        vec![0b111100000],
-        vec![0b010000000, 0b111000000, 0b010000000],
-        vec![0b111000000, 0b001000000, 0b001000000],
-        vec![0b100000000, 0b100000000, 0b100000000, 0b100000000],
-        vec![0b110000000, 0b110000000],
+// Rows count upwards from the floor, so the shapes are flipped after parsing.
+fn blocks() -> Vec<BitGrid> {
+    [
+        "####",
+        ".#.\n###\n.#.",
+        "..#\n..#\n###",
+        "#\n#\n#\n#",
+        "##\n##",
     ]
+    .into_iter()
+    .map(|block| block.parse::<BitGrid>().unwrap().flip_v())
+    .collect_vec()
 }
 
 struct TetrisGrid {
-    grid: Vec<u16>,
-    blocks: Vec<Vec<u16>>,
+    grid: BitGrid,
+    blocks: Vec<BitGrid>,
     block_index: usize,
     jets: Vec<JetDirection>,
     jet_index: usize,
@@ -50,28 +60,28 @@ struct TetrisGrid {
 struct GridHash {
     block_index: usize,
     jet_index: usize,
-    grid_top: [u16; 13],
+    grid_top: Vec<u64>,
 }
 
 impl Display for TetrisGrid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut rows = self.grid.iter().rev().map(|r| format!("{r:09b}")).map(|r| {
-            r.chars()
-                .map(|c| match c {
-                    '0' => '.',
-                    '1' => '#',
-                    _ => unreachable!(),
-                })
-                .join("")
+        // the chamber grows upwards, so the last row is printed first
+        let height = self.height();
+        let renderer = Renderer::new(self.grid.width(), height, |x, y| {
+            if self.grid.get(x, height - 1 - y) {
+                '#'
+            } else {
+                '.'
+            }
         });
-        f.write_str(&rows.join("\n"))
+        write!(f, "{}", renderer)
     }
 }
 
 impl TetrisGrid {
     fn new(jets: Vec<JetDirection>) -> Self {
         Self {
-            grid: vec![0b111111111],
+            grid: BitGrid::new(CHAMBER_WIDTH, 0),
             blocks: blocks(),
             block_index: 0,
             jets,
@@ -80,40 +90,13 @@ impl TetrisGrid {
     }
 
     fn height(&self) -> usize {
-        self.grid
-            .iter()
-            .enumerate()
-            .rev()
-            .find(|&(_, row)| row & 0b011111110 != 0)
-            .map(|(y, _)| y)
-            .unwrap_or(0)
-    }
-
-    fn extend_height(&mut self, new_height: usize) {
-        while self.grid.len() < new_height + 1 {
-            self.grid.push(0b100000001);
-        }
-    }
-
-    fn check_collision(&self, x: u8, y: usize) -> bool {
-        self.grid
-            .iter()
-            .skip(y)
-            .zip(self.blocks.get(self.block_index).unwrap().iter())
-            .any(|(g, b)| g & (b.shr(x)) != 0)
-    }
-
-    fn set_block(&mut self, x: u8, y: usize) {
-        let block = self.blocks.get(self.block_index).unwrap();
-        (0..block.len()).for_each(|i| {
-            self.grid[y + i] |= block[i].shr(x);
-        });
+        self.grid.highest_set_row().map_or(0, |y| y + 1)
     }
 
-    fn next_block(&mut self) -> Vec<u16> {
+    fn next_block(&mut self) -> BitGrid {
         self.block_index += 1;
         self.block_index %= self.blocks.len();
-        self.blocks.get(self.block_index).unwrap().to_owned()
+        self.blocks[self.block_index].clone()
     }
 
     fn next_jet(&mut self) -> &JetDirection {
@@ -123,54 +106,35 @@ impl TetrisGrid {
     }
 
     fn fall_new_block(&mut self) {
-        let mut block_y = self.height() + 4;
-        let mut block_x: u8 = 3;
+        let mut block_y = self.height() as i64 + 3;
+        let mut block_x = 2;
         let block = self.next_block();
-        self.extend_height(block_y + block.len() - 1);
+        self.grid.extend_height(block_y as usize + block.height());
         loop {
-            let jet = self.next_jet();
-            let new_x = match jet {
+            let new_x = match self.next_jet() {
                 JetDirection::Left => block_x - 1,
                 JetDirection::Right => block_x + 1,
             };
-            block_x = if (jet != &JetDirection::Left || block_x != 0)
-                && !self.check_collision(new_x, block_y)
-            {
-                new_x
+            if self.grid.can_place(&block, new_x, block_y) {
+                block_x = new_x;
+            }
+            if self.grid.can_place(&block, block_x, block_y - 1) {
+                block_y -= 1;
             } else {
-                block_x
-            };
-            let new_y = block_y - 1;
-            if self.check_collision(block_x, new_y) {
                 break;
-            } else {
-                block_y = new_y;
             }
         }
-        self.set_block(block_x, block_y);
+        self.grid.place(&block, block_x, block_y);
     }
 
     fn grid_hash(&self) -> GridHash {
-        let mut grid = self.grid.iter().rev();
-        let grid_top = [
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-            grid.next().unwrap_or(&0).to_owned(),
-        ];
+        let window = 13;
         GridHash {
             block_index: self.block_index,
             jet_index: self.jet_index,
-            grid_top,
+            grid_top: self
+                .grid
+                .row_window(self.height().saturating_sub(window), window),
         }
     }
 }
@@ -182,60 +146,28 @@ fn test_falling_block() {
     grid.block_index = grid.blocks.len() - 1;
     grid.jet_index = grid.jets.len() - 1;
 
-    assert_eq!(grid.grid, vec![0b111111111]);
+    assert_eq!(grid.height(), 0);
     grid.fall_new_block();
-    assert_eq!(
-        grid.grid,
-        vec![
-            0b111111111,
-            0b100111101,
-            0b100000001,
-            0b100000001,
-            0b100000001,
-        ]
-    );
+    assert_eq!(grid.to_string(), "..####.");
     grid.fall_new_block();
     assert_eq!(
-        grid.grid,
-        vec![
-            0b111111111,
-            0b100111101,
-            0b100010001,
-            0b100111001,
-            0b100010001,
-            0b100000001,
-            0b100000001,
-            0b100000001,
-        ]
+        grid.to_string(),
+        "...#...
+..###..
+...#...
+..####."
     );
 }
 
-fn solve(grid: &mut TetrisGrid, n: u64) -> usize {
-    let mut cache: HashMap<GridHash, u64> = HashMap::new();
-    let mut height_history: HashMap<u64, usize> = HashMap::new();
-    for i in 0..n {
-        grid.fall_new_block();
-        let state = grid.grid_hash();
-        let height = grid.height();
-        if let Some(last_index) = cache.get(&state) {
-            let last_height = height_history.get(last_index).unwrap();
-            let height_delta = height - last_height;
-            let drop_delta = i - last_index;
-            let drops_remaining = n - i;
-            let loops = drops_remaining / drop_delta;
-            let drops_after_loops = drops_remaining % drop_delta;
-            let height_after_loops = height + (height_delta * loops as usize);
-            let height_during_loop = height_history
-                .get(&(last_index + drops_after_loops - 1))
-                .unwrap();
-            let final_height = height_after_loops + height_during_loop - last_height;
-            return final_height;
-        } else {
-            cache.insert(state, i);
-        }
-        height_history.insert(i, height);
-    }
-    unreachable!()
+// the height after `n` blocks, skipping ahead once the top of the chamber starts repeating
+fn solve(grid: TetrisGrid, n: usize) -> usize {
+    find_cycle(
+        grid,
+        TetrisGrid::fall_new_block,
+        TetrisGrid::grid_hash,
+        TetrisGrid::height,
+    )
+    .extrapolate(n)
 }
 
 impl AocSolution for Part1 {
@@ -250,7 +182,7 @@ impl AocSolution for Part1 {
         grid.block_index = grid.blocks.len() - 1;
         grid.jet_index = grid.jets.len() - 1;
 
-        solve(&mut grid, 2022).to_string()
+        solve(grid, 2022).to_string()
     }
 }
 
@@ -266,7 +198,7 @@ impl AocSolution for Part2 {
         grid.block_index = grid.blocks.len() - 1;
         grid.jet_index = grid.jets.len() - 1;
 
-        solve(&mut grid, 1000000000000).to_string()
+        solve(grid, 1000000000000).to_string()
     }
 }
 
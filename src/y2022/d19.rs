@@ -1,6 +1,9 @@
 use std::fmt::{self, Display};
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    bnb::{available_workers, branch_and_bound_each, BnbResult, Strategy},
+    solution::AocSolution,
+};
 
 #[cfg(test)] // Itertools trait only used in test scope, so import needs test scope
 use itertools::Itertools;
@@ -116,36 +119,33 @@ impl<'a> Display for BotBuildState<'a> {
 }
 
 impl<'a> BotBuildState<'a> {
-    fn max_possible_geodes_in(&self, minutes: i32) -> i32 {
-        self.max_possible_geodes_helper(minutes, 0)
+    fn start(costs: &'a BotCosts) -> Self {
+        BotBuildState {
+            costs,
+            time_step: 0,
+            ores: 0,
+            ore_bots: 1,
+            clays: 0,
+            clay_bots: 0,
+            obsidians: 0,
+            obsidian_bots: 0,
+            geodes: 0,
+            geode_bots: 0,
+        }
+    }
+
+    // what we end up with if nothing else gets built
+    fn geodes_if_idle(&self, minutes: i32) -> i32 {
+        self.geodes + self.geode_bots * (minutes - self.time_step)
     }
 
-    fn max_possible_geodes_helper(&self, minutes: i32, mut max_so_far: i32) -> i32 {
+    // as if we managed to build a geode bot every minute until we finish
+    fn max_possible_geodes(&self, minutes: i32) -> i32 {
         let time_left = minutes - self.time_step;
-        let theoretical_geode_production = (time_left * time_left - 1) / 2;
-        let current_geode_production = self.geodes + self.geode_bots * (minutes - self.time_step);
-        let theoretical_max = current_geode_production + theoretical_geode_production;
-        if self.time_step == minutes - 1 {
-            self.geodes + self.geode_bots
-        } else if theoretical_max <= max_so_far {
-            // if we managed to build a geode bot every minute until we finish,
-            // we still wouldn't beat max_so_far, so give up
-            theoretical_max
-        } else {
-            let next_steps = self.possible_next_steps(minutes);
-            if next_steps.is_empty() {
-                return current_geode_production;
-            }
-            for step in next_steps {
-                max_so_far = step
-                    .max_possible_geodes_helper(minutes, max_so_far)
-                    .max(max_so_far);
-            }
-            max_so_far
-        }
+        self.geodes_if_idle(minutes) + (time_left * time_left - 1) / 2
     }
 
-    fn possible_next_steps(&self, max_minutes: i32) -> Vec<BotBuildState> {
+    fn possible_next_steps(&self, max_minutes: i32) -> Vec<BotBuildState<'a>> {
         let mut result = vec![];
 
         // build geode bot
@@ -260,6 +260,18 @@ impl<'a> BotBuildState<'a> {
     }
 }
 
+// each blueprint is searched on its own, so they can go to different workers
+fn most_geodes(costs: &[BotCosts], minutes: i32) -> Vec<BnbResult<BotBuildState<'_>, i32>> {
+    branch_and_bound_each(
+        costs.iter().map(BotBuildState::start).collect(),
+        available_workers(),
+        Strategy::DepthFirst,
+        |state| state.possible_next_steps(minutes),
+        |state| state.geodes_if_idle(minutes),
+        |state| state.max_possible_geodes(minutes),
+    )
+}
+
 impl AocSolution for Part1 {
     const PART: u32 = 1;
     fn solution_path() -> String {
@@ -268,24 +280,18 @@ impl AocSolution for Part1 {
 
     fn implementation(input: &str) -> String {
         let (_, costs) = parse_input(input).unwrap();
-        costs
+        most_geodes(&costs, 24)
             .iter()
-            .map(|c| {
-                let m = BotBuildState {
-                    costs: c,
-                    time_step: 0,
-                    ores: 0,
-                    ore_bots: 1,
-                    clays: 0,
-                    clay_bots: 0,
-                    obsidians: 0,
-                    obsidian_bots: 0,
-                    geodes: 0,
-                    geode_bots: 0,
-                }
-                .max_possible_geodes_in(24);
-                println!("Blueprint {}: {} geodes, {} score", c.id, m, m * c.id);
-                m * c.id
+            .map(|result| {
+                let (m, id) = (result.value, result.state.costs.id);
+                println!(
+                    "Blueprint {}: {} geodes, {} score, {} nodes searched",
+                    id,
+                    m,
+                    m * id,
+                    result.nodes
+                );
+                m * id
             })
             .sum::<i32>()
             .to_string()
@@ -300,25 +306,15 @@ impl AocSolution for Part2 {
 
     fn implementation(input: &str) -> String {
         let (_, costs) = parse_input(input).unwrap();
-        costs
+        let first_three = &costs[..costs.len().min(3)];
+        most_geodes(first_three, 32)
             .iter()
-            .take(3)
-            .map(|c| {
-                let m = BotBuildState {
-                    costs: c,
-                    time_step: 0,
-                    ores: 0,
-                    ore_bots: 1,
-                    clays: 0,
-                    clay_bots: 0,
-                    obsidians: 0,
-                    obsidian_bots: 0,
-                    geodes: 0,
-                    geode_bots: 0,
-                }
-                .max_possible_geodes_in(32);
-                println!("Blueprint {}: {} geodes", c.id, m);
-                m
+            .map(|result| {
+                println!(
+                    "Blueprint {}: {} geodes, {} nodes searched",
+                    result.state.costs.id, result.value, result.nodes
+                );
+                result.value
             })
             .product::<i32>()
             .to_string()
@@ -345,6 +341,18 @@ fn p2_run() {
     Part2::solve();
 }
 
+#[test]
+fn test_most_geodes() {
+    let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+    let (_, costs) = parse_input(input).unwrap();
+    let results = most_geodes(&costs, 24);
+    assert_eq!(results.iter().map(|r| r.value).collect_vec(), vec![9, 12]);
+    assert!(results.iter().all(|r| r.pruned > 0));
+}
+
 #[test]
 fn test_next_steps() {
     let costs = BotCosts {
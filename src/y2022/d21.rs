@@ -13,7 +13,7 @@ use nom::{
     IResult,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{graph::Graph, solution::AocSolution};
 
 struct Part1 {}
 struct Part2 {}
@@ -72,26 +72,52 @@ fn parse_line(line: &str) -> IResult<&str, (&str, MonkeyYell)> {
     )(line)
 }
 
+// the monkeys `monkey_name` waits on, with an edge from each monkey to the ones waiting on it
+fn dependency_graph<'a>(
+    monkey_name: &'a str,
+    all_monkeys: &HashMap<&'a str, MonkeyYell<'a>>,
+) -> Graph<'a> {
+    let mut graph = Graph::new();
+    graph.intern(monkey_name);
+    let mut to_visit = vec![monkey_name];
+    while let Some(monkey) = to_visit.pop() {
+        if let MonkeyYell::Function(_, a, b) = all_monkeys[monkey] {
+            for dependency in [a, b] {
+                if graph.id(dependency).is_none() {
+                    to_visit.push(dependency);
+                }
+                graph.add_edge(dependency, monkey);
+            }
+        }
+    }
+    graph
+}
+
 fn evaluate_monkey<'a>(
     monkey_name: &'a str,
     all_monkeys: &HashMap<&'a str, MonkeyYell<'a>>,
 ) -> i64 {
-    let monkey_yell = all_monkeys.get(monkey_name).unwrap().clone();
-
-    match monkey_yell {
-        MonkeyYell::Function(f, a, b) => {
-            let a_value = evaluate_monkey(a, all_monkeys);
-            let b_value = evaluate_monkey(b, all_monkeys);
-
-            match f {
-                MonkeyMathFunction::Plus => a_value + b_value,
-                MonkeyMathFunction::Minus => a_value - b_value,
-                MonkeyMathFunction::Times => a_value * b_value,
-                MonkeyMathFunction::DividedBy => a_value / b_value,
+    let graph = dependency_graph(monkey_name, all_monkeys);
+    let order = graph
+        .topological_sort()
+        .expect("monkeys should not wait on each other in a circle");
+    let mut values = HashMap::new();
+    for monkey in graph.names_of(&order) {
+        let value = match &all_monkeys[monkey] {
+            MonkeyYell::Function(f, a, b) => {
+                let (a_value, b_value) = (values[a], values[b]);
+                match f {
+                    MonkeyMathFunction::Plus => a_value + b_value,
+                    MonkeyMathFunction::Minus => a_value - b_value,
+                    MonkeyMathFunction::Times => a_value * b_value,
+                    MonkeyMathFunction::DividedBy => a_value / b_value,
+                }
             }
-        }
-        MonkeyYell::Value(v) => v,
+            MonkeyYell::Value(v) => *v,
+        };
+        values.insert(monkey, value);
     }
+    values[monkey_name]
 }
 
 #[test]
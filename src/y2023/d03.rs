@@ -8,7 +8,7 @@ use nom::{
     multi::many1,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{grid::SquareGrid, solution::AocSolution};
 
 struct Part1 {}
 struct Part2 {}
@@ -20,26 +20,9 @@ enum SchematicPart {
     Number(u32),
 }
 
-#[derive(Debug)]
-struct EngineSchematic {
-    width: usize,
-    height: usize,
-    data: Vec<SchematicPart>,
-}
+type EngineSchematic = SquareGrid<SchematicPart>;
 
 impl EngineSchematic {
-    fn get_pos(&self, index: usize) -> (usize, usize) {
-        (index % self.width, index / self.width)
-    }
-
-    fn get(&self, x: usize, y: usize) -> Option<&SchematicPart> {
-        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
-            self.data.get(y * self.width + x)
-        } else {
-            None
-        }
-    }
-
     fn get_neighbors(&self, x: usize, y: usize) -> Vec<&SchematicPart> {
         let top_left = if x > 0 && y > 0 {
             self.get(x - 1, y - 1)
@@ -202,14 +185,7 @@ fn test_parse_line() {
 }
 
 fn parse_input(input: &str) -> EngineSchematic {
-    let width = input.chars().take_while(|c| !c.is_whitespace()).count();
-    let height = input.lines().count();
-    let data = input.lines().flat_map(parse_line).collect();
-    EngineSchematic {
-        width,
-        height,
-        data,
-    }
+    SquareGrid::from_rows(input.lines().map(parse_line).collect_vec()).unwrap()
 }
 
 impl AocSolution for Part1 {
@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, newline, space1},
@@ -8,7 +7,10 @@ use nom::{
     IResult,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    interval::{Interval, IntervalSet, OffsetMap},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -41,6 +43,10 @@ impl AlmanacMapping {
             .filter(|s| self.contains_destination(s))
             .map(|s| s + self.source_start - self.destination_start)
     }
+
+    fn source_interval(&self) -> Interval<u64> {
+        Interval::new(self.source_start, self.source_start + self.length)
+    }
 }
 
 struct Almanac {
@@ -76,6 +82,24 @@ impl AlmanacItem {
     }
 }
 
+// every value in a set of ranges, mapped a whole range at a time
+struct AlmanacItemRanges {
+    ranges: IntervalSet<u64>,
+}
+
+impl AlmanacItemRanges {
+    // ranges are split wherever they cross the edge of a mapping
+    fn map_source(&self, mappings: &[AlmanacMapping]) -> AlmanacItemRanges {
+        let mut offsets = OffsetMap::new();
+        for mapping in mappings {
+            offsets.insert(mapping.source_interval(), mapping.destination_start);
+        }
+        AlmanacItemRanges {
+            ranges: offsets.map_set(&self.ranges),
+        }
+    }
+}
+
 impl Almanac {
     fn seed_to_location(&self, seed: u64) -> u64 {
         AlmanacItem { value: seed }
@@ -100,6 +124,35 @@ impl Almanac {
             .map_destination_to_source(&self.seed_to_soil)
             .value
     }
+
+    fn seed_ranges(&self) -> IntervalSet<u64> {
+        self.seeds
+            .chunks(2)
+            .map(|seed| Interval::new(seed[0], seed[0] + seed[1]))
+            .collect()
+    }
+
+    fn seed_ranges_to_locations(&self, seeds: IntervalSet<u64>) -> IntervalSet<u64> {
+        AlmanacItemRanges { ranges: seeds }
+            .map_source(&self.seed_to_soil)
+            .map_source(&self.soil_to_fertilizer)
+            .map_source(&self.fertilizer_to_water)
+            .map_source(&self.water_to_light)
+            .map_source(&self.light_to_temperature)
+            .map_source(&self.temperature_to_humidity)
+            .map_source(&self.humidity_to_location)
+            .ranges
+    }
+
+    // tries every location from 0 upwards, only fast enough for the examples
+    #[cfg(test)]
+    fn lowest_seed_range_location_by_scan(&self) -> u64 {
+        let seed_ranges = self.seed_ranges();
+        let some_location = self.seed_to_location(self.seeds[0]);
+        (0..some_location)
+            .find(|loc| seed_ranges.contains(&self.location_to_seed(*loc)))
+            .unwrap()
+    }
 }
 
 fn parse_mappings(input: &str) -> IResult<&str, Vec<AlmanacMapping>> {
@@ -188,22 +241,59 @@ impl AocSolution for Part2 {
 
     fn implementation(input: &str) -> String {
         let almanac = parse_input(input);
-        let seed_ranges = almanac
-            .seeds
-            .chunks(2)
-            .map(|seed| seed[0]..(seed[0] + seed[1]))
-            .collect_vec();
-        let some_location = almanac.seed_to_location(almanac.seeds[0]);
-        (0..some_location)
-            .find(|loc| {
-                let seed = almanac.location_to_seed(*loc);
-                seed_ranges.iter().any(|r| r.contains(&seed))
-            })
-            .unwrap()
+        almanac
+            .seed_ranges_to_locations(almanac.seed_ranges())
+            .intervals()[0]
+            .start
             .to_string()
     }
 }
 
+#[cfg(test)]
+const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+#[test]
+fn test_seed_ranges_match_scan() {
+    let almanac = parse_input(EXAMPLE);
+    let locations = almanac.seed_ranges_to_locations(almanac.seed_ranges());
+    assert_eq!(locations.intervals()[0].start, 46);
+    assert_eq!(almanac.lowest_seed_range_location_by_scan(), 46);
+    assert_eq!(locations.total_len(), almanac.seed_ranges().total_len());
+}
+
 #[test]
 fn p1_pull_examples() {
     Part1::get_examples();
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use itertools::Itertools;
 use nom::{
     bytes::complete::{tag, take},
     character::complete::{newline, one_of},
@@ -8,9 +9,12 @@ use nom::{
     multi::{many1, separated_list1},
     sequence::{separated_pair, tuple},
 };
-use num::integer::lcm;
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    cycle::{find_cycle, Cycle},
+    math::first_alignment,
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -73,6 +77,27 @@ fn count_steps_to_node(
     count
 }
 
+// the node a ghost from `start_node` is on after any number of steps
+fn ghost_cycle<'a>(
+    instructions: &[Instruction],
+    desert_map: &HashMap<&'a str, (&'a str, &'a str)>,
+    start_node: &'a str,
+) -> Cycle<&'a str> {
+    find_cycle(
+        (start_node, 0),
+        |(pos, step)| {
+            let map_node = desert_map[*pos];
+            *pos = match instructions[*step] {
+                Instruction::R => map_node.1,
+                Instruction::L => map_node.0,
+            };
+            *step = (*step + 1) % instructions.len();
+        },
+        |state| *state,
+        |(pos, _)| *pos,
+    )
+}
+
 impl AocSolution for Part1 {
     const PART: u32 = 1;
     fn solution_path() -> String {
@@ -93,11 +118,30 @@ impl AocSolution for Part2 {
 
     fn implementation(input: &str) -> String {
         let (instructions, desert_map) = parse_input(input);
-        desert_map
+        let ghosts = desert_map
             .keys()
             .filter(|key| key.ends_with('A'))
-            .map(|loc| count_steps_to_node(&instructions, &desert_map, loc, "Z"))
-            .reduce(lcm)
+            .map(|loc| ghost_cycle(&instructions, &desert_map, loc))
+            .collect_vec();
+        let on_target = |ghost: &Cycle<&str>, t: usize| ghost.value_at(t).ends_with('Z');
+        // before every ghost is going round in circles the steps have to be checked one by one
+        let settled = ghosts.iter().map(|ghost| ghost.start).max().unwrap();
+        if let Some(t) = (0..settled).find(|t| ghosts.iter().all(|ghost| on_target(ghost, *t))) {
+            return t.to_string();
+        }
+        // after that every ghost is on a target at some offsets into its cycle, so
+        // try every combination of them
+        ghosts
+            .iter()
+            .map(|ghost| {
+                (ghost.start..ghost.start + ghost.length)
+                    .filter(|t| on_target(ghost, *t))
+                    .map(|t| (t as u64, ghost.length as u64))
+                    .collect_vec()
+            })
+            .multi_cartesian_product()
+            .filter_map(|cycles| first_alignment(&cycles))
+            .min()
             .unwrap()
             .to_string()
     }
@@ -1,11 +1,13 @@
-use itertools::Itertools;
 use nom::{
     character::complete::{self, newline, space1},
     error::Error,
     multi::separated_list1,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    poly::{extrapolate_backward, extrapolate_forward},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -19,19 +21,6 @@ fn parse_input(input: &str) -> Vec<Vec<i64>> {
     .1
 }
 
-fn next_in_sequence(numbers: &[i64]) -> i64 {
-    if numbers.iter().all(|n| n == &0) {
-        return 0;
-    }
-    let last = numbers.last().unwrap();
-    let diffs = numbers
-        .iter()
-        .tuple_windows()
-        .map(|(a, b)| b - a)
-        .collect_vec();
-    next_in_sequence(&diffs) + last
-}
-
 impl AocSolution for Part1 {
     const PART: u32 = 1;
     fn solution_path() -> String {
@@ -41,7 +30,7 @@ impl AocSolution for Part1 {
     fn implementation(input: &str) -> String {
         parse_input(input)
             .into_iter()
-            .map(|v| next_in_sequence(&v))
+            .map(|v| extrapolate_forward(&v))
             .sum::<i64>()
             .to_string()
     }
@@ -56,7 +45,7 @@ impl AocSolution for Part2 {
     fn implementation(input: &str) -> String {
         parse_input(input)
             .into_iter()
-            .map(|v| next_in_sequence(&v.into_iter().rev().collect_vec()))
+            .map(|v| extrapolate_backward(&v))
             .sum::<i64>()
             .to_string()
     }
@@ -1,8 +1,7 @@
-use std::collections::HashMap;
-
-use itertools::Itertools;
-
-use crate::common::solution::AocSolution;
+use crate::common::{
+    grid::{Connectivity, Direction, Point2, SquareGrid},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -19,125 +18,87 @@ enum PipeSection {
     BendRightDown,
 }
 
-fn char_to_pipe(c: char) -> PipeSection {
-    match c {
-        '|' => PipeSection::Vertical,
-        '-' => PipeSection::Horizontal,
-        'L' => PipeSection::BendUpRight,
-        'J' => PipeSection::BendLeftUp,
-        '7' => PipeSection::BendDownLeft,
-        'F' => PipeSection::BendRightDown,
-        '.' => PipeSection::Ground,
-        'S' => PipeSection::Start,
-        _ => unreachable!("Unexpected pipe char: {}", c),
+impl TryFrom<char> for PipeSection {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '|' => Ok(PipeSection::Vertical),
+            '-' => Ok(PipeSection::Horizontal),
+            'L' => Ok(PipeSection::BendUpRight),
+            'J' => Ok(PipeSection::BendLeftUp),
+            '7' => Ok(PipeSection::BendDownLeft),
+            'F' => Ok(PipeSection::BendRightDown),
+            '.' => Ok(PipeSection::Ground),
+            'S' => Ok(PipeSection::Start),
+            _ => Err("not a pipe"),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-enum StepDirection {
-    Up,
-    Right,
-    Down,
-    Left,
+impl PipeSection {
+    fn openings(&self) -> &'static [Direction] {
+        match self {
+            PipeSection::Vertical => &[Direction::Up, Direction::Down],
+            PipeSection::Horizontal => &[Direction::Left, Direction::Right],
+            PipeSection::BendUpRight => &[Direction::Up, Direction::Right],
+            PipeSection::BendLeftUp => &[Direction::Left, Direction::Up],
+            PipeSection::BendDownLeft => &[Direction::Down, Direction::Left],
+            PipeSection::BendRightDown => &[Direction::Right, Direction::Down],
+            PipeSection::Start | PipeSection::Ground => &[],
+        }
+    }
 }
 
 struct PipeGrid {
-    width: usize,
-    height: usize,
-    grid: Vec<PipeSection>,
+    grid: SquareGrid<PipeSection>,
+    start: (usize, usize),
 }
 
 #[derive(Debug, Clone)]
-struct PipeStep(StepDirection, (usize, usize));
+struct PipeStep(Direction, (usize, usize));
 
 impl PipeGrid {
-    fn get_pos(&self, index: usize) -> (usize, usize) {
-        (index % self.width, index / self.width)
-    }
-
     fn get(&self, x: usize, y: usize) -> Option<&PipeSection> {
-        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
-            self.grid.get(y * self.width + x)
-        } else {
-            None
-        }
+        self.grid.get(x, y)
     }
 
-    fn start_pos(&self) -> (usize, usize) {
-        let start_index = self
-            .grid
-            .iter()
-            .enumerate()
-            .find(|(_, p)| **p == PipeSection::Start)
-            .map(|(i, _)| i)
-            .unwrap();
-        self.get_pos(start_index)
+    fn first_step(&self) -> PipeStep {
+        let (start_x, start_y) = self.start;
+        Direction::all()
+            .into_iter()
+            .find_map(|direction| {
+                let (x, y) = self.grid.travel(start_x, start_y, direction)?;
+                self.get(x, y)
+                    .filter(|pipe| pipe.openings().contains(&direction.opposite()))
+                    .map(|_| PipeStep(direction, (x, y)))
+            })
+            .expect("Expect some adjacent pipe to point towards the start")
     }
 
-    fn first_step(&self) -> PipeStep {
-        let (start_x, start_y) = self.start_pos();
-        match self.get(start_x + 1, start_y) {
-            Some(PipeSection::Horizontal)
-            | Some(PipeSection::BendDownLeft)
-            | Some(PipeSection::BendLeftUp) => {
-                return PipeStep(StepDirection::Right, (start_x + 1, start_y))
-            }
-            _ => {}
-        }
-        match self.get(start_x, start_y + 1) {
-            Some(PipeSection::Vertical)
-            | Some(PipeSection::BendRightDown)
-            | Some(PipeSection::BendDownLeft) => {
-                return PipeStep(StepDirection::Down, (start_x, start_y + 1))
-            }
-            _ => {}
-        }
-        match self.get(start_x - 1, start_y) {
-            Some(PipeSection::Horizontal)
-            | Some(PipeSection::BendUpRight)
-            | Some(PipeSection::BendRightDown) => {
-                return PipeStep(StepDirection::Left, (start_x - 1, start_y))
-            }
-            _ => {}
-        }
-        match self.get(start_x, start_y - 1) {
-            Some(PipeSection::Vertical)
-            | Some(PipeSection::BendLeftUp)
-            | Some(PipeSection::BendUpRight) => {
-                return PipeStep(StepDirection::Up, (start_x, start_y - 1))
-            }
-            _ => {}
+    fn next_step(&self, PipeStep(step, (x, y)): &PipeStep) -> PipeStep {
+        let openings = self.get(*x, *y).unwrap().openings();
+        if !openings.contains(&step.opposite()) {
+            unreachable!("Unexpected step");
         }
-        unreachable!("Expect some adjacent pipe to point towards the start")
+        let direction = *openings.iter().find(|d| **d != step.opposite()).unwrap();
+        PipeStep(direction, self.grid.travel(*x, *y, direction).unwrap())
     }
 
-    fn next_step(&self, PipeStep(step, (x, y)): &PipeStep) -> PipeStep {
-        let x = *x;
-        let y = *y;
-        let current_pipe = self.get(x, y).unwrap();
-        match (step, current_pipe) {
-            (StepDirection::Up, PipeSection::Vertical)
-            | (StepDirection::Right, PipeSection::BendLeftUp)
-            | (StepDirection::Left, PipeSection::BendUpRight) => {
-                PipeStep(StepDirection::Up, (x, y - 1))
-            }
-            (StepDirection::Right, PipeSection::Horizontal)
-            | (StepDirection::Up, PipeSection::BendRightDown)
-            | (StepDirection::Down, PipeSection::BendUpRight) => {
-                PipeStep(StepDirection::Right, (x + 1, y))
-            }
-            (StepDirection::Down, PipeSection::Vertical)
-            | (StepDirection::Left, PipeSection::BendRightDown)
-            | (StepDirection::Right, PipeSection::BendDownLeft) => {
-                PipeStep(StepDirection::Down, (x, y + 1))
+    // the loop drawn at double resolution, so that squeezing between two pipes
+    // becomes a gap the outside can be flood filled through
+    fn loop_walls(&self) -> SquareGrid<bool> {
+        let mut walls =
+            SquareGrid::from_fn(2 * self.grid.width + 1, 2 * self.grid.height + 1, |_, _| {
+                false
+            });
+        for PipeStep(direction, (x, y)) in self.path() {
+            let pipe = Point2::new(2 * x as i64 + 1, 2 * y as i64 + 1);
+            for p in [pipe, pipe - direction.delta()] {
+                walls[(p.x as usize, p.y as usize)] = true;
             }
-            (StepDirection::Left, PipeSection::Horizontal)
-            | (StepDirection::Up, PipeSection::BendDownLeft)
-            | (StepDirection::Down, PipeSection::BendLeftUp) => {
-                PipeStep(StepDirection::Left, (x - 1, y))
-            }
-            _ => unreachable!("Unexpected step"),
         }
+        walls
     }
 
     fn path(&self) -> impl Iterator<Item = PipeStep> + '_ {
@@ -180,13 +141,11 @@ impl<'a> Iterator for PipePath<'a> {
 }
 
 fn parse_input(input: &str) -> PipeGrid {
+    let (grid, markers) =
+        SquareGrid::parse_with_markers(input, &['S'], PipeSection::try_from).unwrap();
     PipeGrid {
-        width: input.chars().take_while(|&c| c != '\n').count(),
-        height: input.lines().count(),
-        grid: input
-            .lines()
-            .flat_map(|line| line.chars().map(char_to_pipe))
-            .collect_vec(),
+        grid,
+        start: markers[&'S'][0],
     }
 }
 
@@ -209,40 +168,13 @@ impl AocSolution for Part2 {
 
     fn implementation(input: &str) -> String {
         let grid = parse_input(input);
-        let pipe_positions: HashMap<(usize, usize), &PipeSection> = HashMap::from_iter(
-            grid.path()
-                .map(|p| (p.1, grid.get(p.1 .0, p.1 .1).unwrap())),
-        );
-        let mut count = 0;
-        for (y, line) in input.lines().enumerate() {
-            let mut vert_count = 0;
-            let mut last_corner = None;
-            for (x, _) in line.chars().enumerate() {
-                if let Some(pipe) = pipe_positions.get(&(x, y)) {
-                    // bug: probably gives a wrong answer if the Start pipe is part of a vertical s-bend
-                    // but it does give the correct answer if the Start pipe is part of a U-bend
-                    if matches!(pipe, PipeSection::Vertical) {
-                        vert_count += 1;
-                    } else if matches!(pipe, PipeSection::BendUpRight | PipeSection::BendRightDown)
-                    {
-                        last_corner = Some(pipe);
-                    } else if matches!(pipe, PipeSection::BendDownLeft) {
-                        if matches!(last_corner, Some(PipeSection::BendUpRight)) {
-                            vert_count += 1;
-                        }
-                        last_corner = None
-                    } else if matches!(pipe, PipeSection::BendLeftUp) {
-                        if matches!(last_corner, Some(PipeSection::BendRightDown)) {
-                            vert_count += 1;
-                        }
-                        last_corner = None
-                    }
-                } else if vert_count % 2 == 1 {
-                    count += 1;
-                }
-            }
-        }
-        count.to_string()
+        let walls = grid.loop_walls();
+        let outside = walls.reachable_from_border(Connectivity::Four, |wall| !wall);
+        (0..grid.grid.height)
+            .flat_map(|y| (0..grid.grid.width).map(move |x| (2 * x + 1, 2 * y + 1)))
+            .filter(|(x, y)| !walls[(*x, *y)] && !outside.get(*x, *y))
+            .count()
+            .to_string()
     }
 }
 
@@ -10,7 +10,7 @@ use nom::{
     sequence::separated_pair,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{memo::Memo, solution::AocSolution};
 
 struct Part1 {}
 struct Part2 {}
@@ -28,11 +28,11 @@ struct RecordRow {
     cluster_sizes: Vec<u32>,
 }
 
+type PossibilityKey = (usize, Option<SpringRecord>, usize, u32, bool);
+
 impl RecordRow {
     fn count_possibilities(&self) -> usize {
-        let mut cache: HashMap<(usize, Option<SpringRecord>, usize, u32, bool), usize> =
-            HashMap::new();
-        self.count_possibilities_helper(0, None, 0, 0, false, &mut cache)
+        self.count_possibilities_helper(0, None, 0, 0, false, &mut Memo::new())
     }
 
     fn count_possibilities_helper(
@@ -42,7 +42,7 @@ impl RecordRow {
         cluster_index: usize,
         cluster_count: u32,
         in_cluster: bool,
-        cache: &mut HashMap<(usize, Option<SpringRecord>, usize, u32, bool), usize>,
+        memo: &mut Memo<PossibilityKey, usize>,
     ) -> usize {
         if spring_index == self.springs.len() {
             return if cluster_index == self.cluster_sizes.len()
@@ -73,70 +73,67 @@ impl RecordRow {
             cluster_count,
             in_cluster,
         );
-        if let Some(&x) = cache.get(&cache_key) {
-            return x;
-        }
-        let current_spring = force_spring.unwrap_or(self.springs[spring_index]);
-        let answer = match current_spring {
-            SpringRecord::Damaged => {
-                if cluster_count < self.cluster_sizes[cluster_index] {
-                    self.count_possibilities_helper(
-                        spring_index + 1,
-                        None,
-                        cluster_index,
-                        cluster_count + 1,
-                        true,
-                        cache,
-                    )
-                } else {
-                    0
-                }
-            }
-            SpringRecord::Empty => {
-                if in_cluster {
-                    if cluster_count == self.cluster_sizes[cluster_index] {
+        memo.get_or_compute(cache_key, |memo| {
+            let current_spring = force_spring.unwrap_or(self.springs[spring_index]);
+            match current_spring {
+                SpringRecord::Damaged => {
+                    if cluster_count < self.cluster_sizes[cluster_index] {
                         self.count_possibilities_helper(
                             spring_index + 1,
                             None,
-                            cluster_index + 1,
-                            0,
-                            false,
-                            cache,
+                            cluster_index,
+                            cluster_count + 1,
+                            true,
+                            memo,
                         )
                     } else {
                         0
                     }
-                } else {
+                }
+                SpringRecord::Empty => {
+                    if in_cluster {
+                        if cluster_count == self.cluster_sizes[cluster_index] {
+                            self.count_possibilities_helper(
+                                spring_index + 1,
+                                None,
+                                cluster_index + 1,
+                                0,
+                                false,
+                                memo,
+                            )
+                        } else {
+                            0
+                        }
+                    } else {
+                        self.count_possibilities_helper(
+                            spring_index + 1,
+                            None,
+                            cluster_index,
+                            cluster_count,
+                            in_cluster,
+                            memo,
+                        )
+                    }
+                }
+                SpringRecord::Unknown => {
                     self.count_possibilities_helper(
-                        spring_index + 1,
-                        None,
+                        spring_index,
+                        Some(SpringRecord::Damaged),
                         cluster_index,
                         cluster_count,
                         in_cluster,
-                        cache,
+                        memo,
+                    ) + self.count_possibilities_helper(
+                        spring_index,
+                        Some(SpringRecord::Empty),
+                        cluster_index,
+                        cluster_count,
+                        in_cluster,
+                        memo,
                     )
                 }
             }
-            SpringRecord::Unknown => {
-                self.count_possibilities_helper(
-                    spring_index,
-                    Some(SpringRecord::Damaged),
-                    cluster_index,
-                    cluster_count,
-                    in_cluster,
-                    cache,
-                ) + self.count_possibilities_helper(
-                    spring_index,
-                    Some(SpringRecord::Empty),
-                    cluster_index,
-                    cluster_count,
-                    in_cluster,
-                    cache,
-                )
-            }
-        };
-        cache.insert(cache_key, answer);
-        answer
+        })
     }
 
     fn into_part_2(self) -> RecordRow {
@@ -155,6 +152,8 @@ impl RecordRow {
 fn test_count_2() {
     assert_eq!(1, parse_line("???.### 1,1,3").count_possibilities());
     assert_eq!(10, parse_line("?###???????? 3,2,1").count_possibilities());
+    let unfolded = parse_line("?###???????? 3,2,1").into_part_2();
+    assert_eq!(506250, unfolded.count_possibilities());
 }
 
 fn parse_line(line: &str) -> RecordRow {
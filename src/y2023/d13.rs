@@ -1,13 +1,17 @@
 use itertools::Itertools;
-use strsim::hamming;
+use strsim::generic_hamming;
 
-use crate::common::solution::AocSolution;
+use crate::common::{grid::SquareGrid, solution::AocSolution};
 
 struct Part1 {}
 struct Part2 {}
 
-fn find_horizontal_mirror(input: &str) -> usize {
-    let lines = input.lines().collect_vec();
+fn parse_pattern(input: &str) -> SquareGrid<char> {
+    input.parse().unwrap()
+}
+
+fn find_horizontal_mirror(pattern: &SquareGrid<char>) -> usize {
+    let lines = pattern.rows().collect_vec();
     'outer: for i in 0..lines.len() - 1 {
         if lines[i] != lines[i + 1] {
             continue;
@@ -27,7 +31,7 @@ fn find_horizontal_mirror(input: &str) -> usize {
 fn test_find_horizontal_mirror() {
     assert_eq!(
         0,
-        find_horizontal_mirror(
+        find_horizontal_mirror(&parse_pattern(
             "#.##..##.
 ..#.##.#.
 ##......#
@@ -35,11 +39,11 @@ fn test_find_horizontal_mirror() {
 ..#.##.#.
 ..##..##.
 #.#.##.#."
-        )
+        ))
     );
     assert_eq!(
         4,
-        find_horizontal_mirror(
+        find_horizontal_mirror(&parse_pattern(
             "#...##..#
 #....#..#
 ..##..###
@@ -47,22 +51,15 @@ fn test_find_horizontal_mirror() {
 #####.##.
 ..##..###
 #....#..#"
-        )
+        ))
     );
 }
 
-fn transpose_string(input: &str) -> String {
-    let mut line_iters = input.lines().map(|l| l.chars()).collect_vec();
-    let width = input.lines().next().unwrap().len();
-    (0..width)
-        .map(|_| line_iters.iter_mut().map(|l| (*l).next().unwrap()).join(""))
-        .join("\n")
-}
-
 #[test]
-fn test_transpose_string() {
+fn test_transpose_pattern() {
     assert_eq!(
-        "#.##..#
+        parse_pattern(
+            "#.##..#
 ..##...
 ##..###
 #....#.
@@ -70,8 +67,9 @@ fn test_transpose_string() {
 .#..#.#
 #....#.
 ##..###
-..##...",
-        transpose_string(
+..##..."
+        ),
+        parse_pattern(
             "#.##..##.
 ..#.##.#.
 ##......#
@@ -80,18 +78,22 @@ fn test_transpose_string() {
 ..##..##.
 #.#.##.#."
         )
+        .transpose()
     );
     assert_eq!(
         5,
-        find_horizontal_mirror(&transpose_string(
-            "#.##..##.
+        find_horizontal_mirror(
+            &parse_pattern(
+                "#.##..##.
 ..#.##.#.
 ##......#
 ##......#
 ..#.##.#.
 ..##..##.
 #.#.##.#."
-        ))
+            )
+            .transpose()
+        )
     );
 }
 
@@ -104,12 +106,13 @@ impl AocSolution for Part1 {
     fn implementation(input: &str) -> String {
         input
             .split("\n\n")
-            .map(|grid| {
-                let horizontal_mirror = find_horizontal_mirror(grid);
+            .map(|pattern| {
+                let pattern = parse_pattern(pattern);
+                let horizontal_mirror = find_horizontal_mirror(&pattern);
                 if horizontal_mirror != 0 {
                     100 * horizontal_mirror
                 } else {
-                    find_horizontal_mirror(&transpose_string(grid))
+                    find_horizontal_mirror(&pattern.transpose())
                 }
             })
             .sum::<usize>()
@@ -117,13 +120,13 @@ impl AocSolution for Part1 {
     }
 }
 
-fn find_horizontal_mirror_smudged(input: &str) -> usize {
-    let lines = input.lines().collect_vec();
+fn find_horizontal_mirror_smudged(pattern: &SquareGrid<char>) -> usize {
+    let lines = pattern.rows().collect_vec();
     'outer: for i in 0..lines.len() - 1 {
         let mut smudge_found = false;
         let a = lines[i];
         let b = lines[i + 1];
-        let difference = hamming(a, b).unwrap();
+        let difference = generic_hamming(a, b).unwrap();
         if difference == 1 {
             smudge_found = true;
         } else if a != b {
@@ -133,7 +136,7 @@ fn find_horizontal_mirror_smudged(input: &str) -> usize {
         for j in 1..possible_mirror_size {
             let a = lines[i - j];
             let b = lines[i + 1 + j];
-            let difference = hamming(a, b).unwrap();
+            let difference = generic_hamming(a, b).unwrap();
             if difference == 1 && !smudge_found {
                 smudge_found = true;
                 continue;
@@ -152,7 +155,7 @@ fn find_horizontal_mirror_smudged(input: &str) -> usize {
 fn test_find_horizontal_mirror_smudged() {
     assert_eq!(
         3,
-        find_horizontal_mirror_smudged(
+        find_horizontal_mirror_smudged(&parse_pattern(
             "#.##..##.
 ..#.##.#.
 ##......#
@@ -160,11 +163,11 @@ fn test_find_horizontal_mirror_smudged() {
 ..#.##.#.
 ..##..##.
 #.#.##.#."
-        )
+        ))
     );
     assert_eq!(
         1,
-        find_horizontal_mirror_smudged(
+        find_horizontal_mirror_smudged(&parse_pattern(
             "#...##..#
 #....#..#
 ..##..###
@@ -172,19 +175,22 @@ fn test_find_horizontal_mirror_smudged() {
 #####.##.
 ..##..###
 #....#..#"
-        )
+        ))
     );
     assert_eq!(
         0,
-        find_horizontal_mirror_smudged(&transpose_string(
-            "#.##..##.
+        find_horizontal_mirror_smudged(
+            &parse_pattern(
+                "#.##..##.
 ..#.##.#.
 ##......#
 ##......#
 ..#.##.#.
 ..##..##.
 #.#.##.#."
-        ))
+            )
+            .transpose()
+        )
     )
 }
 
@@ -197,12 +203,13 @@ impl AocSolution for Part2 {
     fn implementation(input: &str) -> String {
         input
             .split("\n\n")
-            .map(|grid| {
-                let horizontal_mirror = find_horizontal_mirror_smudged(grid);
+            .map(|pattern| {
+                let pattern = parse_pattern(pattern);
+                let horizontal_mirror = find_horizontal_mirror_smudged(&pattern);
                 if horizontal_mirror != 0 {
                     100 * horizontal_mirror
                 } else {
-                    find_horizontal_mirror_smudged(&transpose_string(grid))
+                    find_horizontal_mirror_smudged(&pattern.transpose())
                 }
             })
             .sum::<usize>()
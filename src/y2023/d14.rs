@@ -1,119 +1,88 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Display,
-    mem::swap,
-};
+use std::fmt::Display;
 
-use itertools::Itertools;
-
-use crate::common::solution::AocSolution;
+use crate::common::{cycle::find_cycle, grid::SquareGrid, solution::AocSolution};
 
 struct Part1 {}
 struct Part2 {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Rock {
+    Round,
+    Square,
+    Empty,
+}
+
+impl TryFrom<char> for Rock {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'O' => Ok(Rock::Round),
+            '#' => Ok(Rock::Square),
+            '.' => Ok(Rock::Empty),
+            _ => Err("not a rock or empty space"),
+        }
+    }
+}
+
+impl Display for Rock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Rock::Round => "O",
+            Rock::Square => "#",
+            Rock::Empty => ".",
+        })
+    }
+}
+
 struct RocksGrid {
-    round_rocks: HashSet<(usize, usize)>,
-    square_rocks: HashSet<(usize, usize)>,
-    width: usize,
-    height: usize,
+    grid: SquareGrid<Rock>,
 }
 
 impl Display for RocksGrid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(
-            &(0..self.height)
-                .map(|y| {
-                    (0..self.width)
-                        .map(|x| {
-                            let p = (x, y);
-                            let r = self.round_rocks.contains(&p);
-                            let s = self.square_rocks.contains(&p);
-                            if r && s {
-                                '!'
-                            } else if r {
-                                'O'
-                            } else if s {
-                                '#'
-                            } else {
-                                '.'
-                            }
-                        })
-                        .join("")
-                })
-                .join("\n"),
-        )
+        write!(f, "{}", self.grid)
     }
 }
 
 impl RocksGrid {
-    fn collision(&self, p: (usize, usize)) -> bool {
-        self.round_rocks.contains(&p) || self.square_rocks.contains(&p)
-    }
-
     fn tilt_north(&mut self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.round_rocks.remove(&(x, y)) {
-                    let mut placed = false;
-                    'move_rock: for dy in 0..y {
-                        if self.collision((x, y - dy - 1)) {
-                            self.round_rocks.insert((x, y - dy));
-                            placed = true;
-                            break 'move_rock;
-                        }
-                    }
-                    if !placed {
-                        self.round_rocks.insert((x, 0));
+        for x in 0..self.grid.width {
+            let mut free_y = 0;
+            for y in 0..self.grid.height {
+                match self.grid[(x, y)] {
+                    Rock::Square => free_y = y + 1,
+                    Rock::Round => {
+                        self.grid[(x, y)] = Rock::Empty;
+                        self.grid[(x, free_y)] = Rock::Round;
+                        free_y += 1;
                     }
+                    Rock::Empty => {}
                 }
             }
         }
     }
 
     fn count_load(&self) -> usize {
-        self.round_rocks.iter().map(|(_, y)| self.height - y).sum()
+        self.grid
+            .iter_with_pos()
+            .filter(|(_, rock)| **rock == Rock::Round)
+            .map(|((_, y), _)| self.grid.height - y)
+            .sum()
     }
 
-    fn rotate_clockwise(&mut self) {
-        self.round_rocks = HashSet::from_iter(
-            self.round_rocks
-                .iter()
-                .map(|(x, y)| (self.height - y - 1, *x)),
-        );
-        self.square_rocks = HashSet::from_iter(
-            self.square_rocks
-                .iter()
-                .map(|(x, y)| (self.height - y - 1, *x)),
-        );
-        swap(&mut self.width, &mut self.height);
+    // tilts north, west, south and east in turn
+    fn spin_cycle(&mut self) {
+        for _ in 0..4 {
+            self.tilt_north();
+            self.grid = self.grid.rotate_cw();
+        }
     }
 }
 
 fn parse_input(input: &str) -> RocksGrid {
-    let mut round_rocks = HashSet::new();
-    let mut square_rocks = HashSet::new();
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            match c {
-                'O' => {
-                    round_rocks.insert((x, y));
-                }
-                '#' => {
-                    square_rocks.insert((x, y));
-                }
-                _ => {}
-            };
-        }
-    }
-
     RocksGrid {
-        round_rocks,
-        square_rocks,
-        width,
-        height,
+        grid: input.parse().unwrap(),
     }
 }
 
@@ -137,35 +106,15 @@ impl AocSolution for Part2 {
     }
 
     fn implementation(input: &str) -> String {
-        let mut rocks = parse_input(input);
-        let mut visited_turn = HashMap::new();
-        let mut turns_cache = HashMap::new();
-        let mut turns = 0;
-        let loop_size;
-        loop {
-            rocks.tilt_north(); // north
-            rocks.rotate_clockwise();
-            rocks.tilt_north(); // west
-            rocks.rotate_clockwise();
-            rocks.tilt_north(); // south
-            rocks.rotate_clockwise();
-            rocks.tilt_north(); // east
-            rocks.rotate_clockwise();
-            turns += 1;
-            let cache_key = rocks.round_rocks.iter().copied().sorted().collect_vec();
-            if visited_turn.contains_key(&cache_key) {
-                loop_size = turns - visited_turn[&cache_key];
-                break;
-            }
-            visited_turn.insert(cache_key, turns);
-            turns_cache.insert(turns, rocks.count_load());
-        }
-        let loop_target = 1_000_000_000;
-        let loop_extra = (loop_target - turns) % loop_size;
-        turns_cache
-            .get(&(turns - loop_size + loop_extra))
-            .unwrap()
-            .to_string()
+        let rocks = parse_input(input);
+        find_cycle(
+            rocks,
+            RocksGrid::spin_cycle,
+            |rocks| rocks.grid.data.clone(),
+            RocksGrid::count_load,
+        )
+        .value_at(1_000_000_000)
+        .to_string()
     }
 }
 
@@ -3,7 +3,10 @@ use std::collections::HashSet;
 use itertools::Itertools;
 
 use crate::common::{
-    grid::{Direction, SquareGrid},
+    grid::{
+        render::{Color, Layer},
+        Direction, SquareGrid,
+    },
     solution::AocSolution,
 };
 
@@ -34,26 +37,24 @@ impl LightBeam {
         match grid.get(self.x, self.y) {
             Some(mirror) => match mirror {
                 MirrorSquare::Empty => vec![self.direction],
-                MirrorSquare::VerticalSplitter => match self.direction {
-                    Direction::Up | Direction::Down => vec![self.direction],
-                    Direction::Left | Direction::Right => vec![Direction::Up, Direction::Down],
-                },
-                MirrorSquare::HorizontalSplitter => match self.direction {
-                    Direction::Up | Direction::Down => vec![Direction::Left, Direction::Right],
-                    Direction::Left | Direction::Right => vec![self.direction],
-                },
-                MirrorSquare::DiagonalDownMirror => match self.direction {
-                    Direction::Up => vec![Direction::Left],
-                    Direction::Down => vec![Direction::Right],
-                    Direction::Left => vec![Direction::Up],
-                    Direction::Right => vec![Direction::Down],
-                },
-                MirrorSquare::DiagonalUpMirror => match self.direction {
-                    Direction::Up => vec![Direction::Right],
-                    Direction::Down => vec![Direction::Left],
-                    Direction::Left => vec![Direction::Down],
-                    Direction::Right => vec![Direction::Up],
-                },
+                MirrorSquare::VerticalSplitter if self.direction.is_horizontal() => {
+                    vec![self.direction.turn_left(), self.direction.turn_right()]
+                }
+                MirrorSquare::HorizontalSplitter if self.direction.is_vertical() => {
+                    vec![self.direction.turn_left(), self.direction.turn_right()]
+                }
+                MirrorSquare::VerticalSplitter | MirrorSquare::HorizontalSplitter => {
+                    vec![self.direction]
+                }
+                // `\` turns Up into Left and Right into Down
+                MirrorSquare::DiagonalDownMirror if self.direction.is_vertical() => {
+                    vec![self.direction.turn_left()]
+                }
+                MirrorSquare::DiagonalDownMirror => vec![self.direction.turn_right()],
+                MirrorSquare::DiagonalUpMirror if self.direction.is_vertical() => {
+                    vec![self.direction.turn_right()]
+                }
+                MirrorSquare::DiagonalUpMirror => vec![self.direction.turn_left()],
             },
             None => vec![],
         }
@@ -66,28 +67,25 @@ impl LightBeam {
     }
 }
 
-fn parse_input(input: &str) -> SquareGrid<MirrorSquare> {
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
-    let data = input
-        .lines()
-        .flat_map(|l| l.chars())
-        .map(|c| match c {
-            '.' => MirrorSquare::Empty,
-            '|' => MirrorSquare::VerticalSplitter,
-            '-' => MirrorSquare::HorizontalSplitter,
-            '\\' => MirrorSquare::DiagonalDownMirror,
-            '/' => MirrorSquare::DiagonalUpMirror,
-            _ => unreachable!(),
-        })
-        .collect_vec();
-    SquareGrid {
-        width,
-        height,
-        data,
+impl TryFrom<char> for MirrorSquare {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(MirrorSquare::Empty),
+            '|' => Ok(MirrorSquare::VerticalSplitter),
+            '-' => Ok(MirrorSquare::HorizontalSplitter),
+            '\\' => Ok(MirrorSquare::DiagonalDownMirror),
+            '/' => Ok(MirrorSquare::DiagonalUpMirror),
+            _ => Err("not a mirror"),
+        }
     }
 }
 
+fn parse_input(input: &str) -> SquareGrid<MirrorSquare> {
+    input.parse().unwrap()
+}
+
 fn energized_count(
     grid: &SquareGrid<MirrorSquare>,
     x: usize,
@@ -122,30 +120,30 @@ impl AocSolution for Part1 {
 }
 
 #[allow(dead_code)]
-fn print_light_path(grid: SquareGrid<MirrorSquare>, visited: &HashSet<LightBeam>) {
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            match grid.get(x, y) {
-                Some(MirrorSquare::Empty) => {
-                    let light_visited = visited
-                        .iter()
-                        .filter(|l| l.x == x && l.y == y)
-                        .collect_vec();
-                    match light_visited.len() {
-                        0 => print!("."),
-                        1 => print!("{}", light_visited[0].direction),
-                        c => print!("{}", c),
-                    }
-                }
-                Some(MirrorSquare::VerticalSplitter) => print!("|"),
-                Some(MirrorSquare::HorizontalSplitter) => print!("-"),
-                Some(MirrorSquare::DiagonalDownMirror) => print!("\\"),
-                Some(MirrorSquare::DiagonalUpMirror) => print!("/"),
-                None => unreachable!(),
-            }
-        }
-        println!();
-    }
+fn print_light_path(grid: &SquareGrid<MirrorSquare>, visited: &HashSet<LightBeam>) {
+    let beams = visited.iter().into_group_map_by(|beam| (beam.x, beam.y));
+    // empty squares show the direction of the beam passing through, or how many beams do
+    let glyphs = beams
+        .iter()
+        .filter(|(p, _)| matches!(grid[**p], MirrorSquare::Empty))
+        .map(|(p, beams)| match beams[..] {
+            [beam] => (*p, beam.direction.to_string().chars().next().unwrap()),
+            _ => (*p, char::from_digit(beams.len() as u32, 10).unwrap()),
+        })
+        .collect_vec();
+    grid.renderer(|square| match square {
+        MirrorSquare::Empty => '.',
+        MirrorSquare::VerticalSplitter => '|',
+        MirrorSquare::HorizontalSplitter => '-',
+        MirrorSquare::DiagonalDownMirror => '\\',
+        MirrorSquare::DiagonalUpMirror => '/',
+    })
+    .layer(
+        Layer::new(None, Some(Color::Yellow))
+            .with_cells(beams.keys().copied())
+            .with_glyphs(glyphs),
+    )
+    .print();
 }
 
 impl AocSolution for Part2 {
@@ -1,15 +1,8 @@
-use std::collections::{BinaryHeap, HashSet};
-
 use itertools::Itertools;
-use nom::{
-    character::complete::{newline, one_of},
-    combinator::map,
-    error::Error,
-    multi::{many1, separated_list1},
-};
 
 use crate::common::{
     grid::{Direction, SquareGrid},
+    search::dijkstra,
     solution::AocSolution,
 };
 
@@ -17,44 +10,17 @@ struct Part1 {}
 struct Part2 {}
 
 fn parse_input(input: &str) -> SquareGrid<u64> {
-    let data = separated_list1(
-        newline::<_, Error<_>>,
-        many1(map(one_of("0123456789"), |c| {
-            c.to_digit(10).unwrap() as u64
-        })),
-    )(input)
+    SquareGrid::parse(input, |c| {
+        c.to_digit(10).map(u64::from).ok_or("not a digit")
+    })
     .unwrap()
-    .1;
-    SquareGrid {
-        width: data[0].len(),
-        height: data.len(),
-        data: data.into_iter().flatten().collect_vec(),
-    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct CrucibleStep {
+struct Crucible {
     x: usize,
     y: usize,
     direction: Direction,
-    steps_same_direction: u64,
-    coolness: u64,
-}
-
-impl PartialOrd for CrucibleStep {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for CrucibleStep {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.coolness.cmp(&other.coolness) {
-            std::cmp::Ordering::Less => std::cmp::Ordering::Greater,
-            std::cmp::Ordering::Equal => std::cmp::Ordering::Equal,
-            std::cmp::Ordering::Greater => std::cmp::Ordering::Less,
-        }
-    }
 }
 
 fn best_crucible_path_coolness(
@@ -62,71 +28,44 @@ fn best_crucible_path_coolness(
     min_steps_same_direction: usize,
     max_steps_same_direction: usize,
 ) -> u64 {
-    let mut queue = BinaryHeap::new();
-    let mut visited: HashSet<(usize, usize, Direction, u64)> = HashSet::new();
-    queue.push(CrucibleStep {
+    let starts = [Direction::Right, Direction::Down].map(|direction| Crucible {
         x: 0,
         y: 0,
-        direction: Direction::Right,
-        steps_same_direction: 0,
-        coolness: 0,
+        direction,
     });
-    queue.push(CrucibleStep {
-        x: 0,
-        y: 0,
-        direction: Direction::Down,
-        steps_same_direction: 0,
-        coolness: 0,
-    });
-    visited.extend(
-        queue
-            .iter()
-            .map(|s| (s.x, s.y, s.direction, s.steps_same_direction)),
-    );
-    while let Some(step) = queue.pop() {
-        if step.x == grid.width - 1 && step.y == grid.height - 1 {
-            return step.coolness;
-        }
-        let directions = match step.direction {
-            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
-            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
-        };
-        for direction in directions {
-            let next_steps = (min_steps_same_direction..=max_steps_same_direction)
-                .filter_map(|i| crucible_travel(grid, &step, direction, i))
-                .filter(|s| !visited.contains(&(s.x, s.y, s.direction, s.steps_same_direction)))
-                .collect_vec();
-            visited.extend(
-                next_steps
-                    .iter()
-                    .map(|s| (s.x, s.y, s.direction, s.steps_same_direction)),
-            );
-            queue.extend(next_steps.into_iter());
-        }
-    }
-    unreachable!()
+    // every move turns and then goes straight, so the steps taken in one direction never need tracking
+    let successors = |crucible: &Crucible| {
+        [
+            crucible.direction.turn_left(),
+            crucible.direction.turn_right(),
+        ]
+        .into_iter()
+        .flat_map(|direction| {
+            (min_steps_same_direction..=max_steps_same_direction)
+                .filter_map(move |steps| crucible_travel(grid, crucible, direction, steps))
+        })
+        .collect_vec()
+    };
+    dijkstra(starts, successors, |crucible| {
+        crucible.x == grid.width - 1 && crucible.y == grid.height - 1
+    })
+    .expect("the bottom right corner should be reachable")
+    .cost
 }
 
 fn crucible_travel(
     grid: &SquareGrid<u64>,
-    from: &CrucibleStep,
-    new_direction: Direction,
+    from: &Crucible,
+    direction: Direction,
     steps: usize,
-) -> Option<CrucibleStep> {
-    let mut next_step = from.clone();
-    next_step.steps_same_direction = 0;
-    next_step.direction = new_direction;
+) -> Option<(Crucible, u64)> {
+    let (mut x, mut y) = (from.x, from.y);
+    let mut coolness = 0;
     for _ in 0..steps {
-        if let Some((x, y)) = grid.travel(next_step.x, next_step.y, next_step.direction) {
-            next_step.x = x;
-            next_step.y = y;
-            next_step.steps_same_direction += 1;
-            next_step.coolness += grid.get(x, y).unwrap();
-        } else {
-            return None;
-        }
+        (x, y) = grid.travel(x, y, direction)?;
+        coolness += grid[(x, y)];
     }
-    Some(next_step)
+    Some((Crucible { x, y, direction }, coolness))
 }
 
 impl AocSolution for Part1 {
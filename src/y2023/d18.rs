@@ -1,6 +1,3 @@
-use std::collections::HashSet;
-
-use itertools::Itertools;
 use nom::{
     bytes::complete::{tag, take},
     character::complete::{self, newline, one_of, space1},
@@ -11,7 +8,7 @@ use nom::{
 };
 
 use crate::common::{
-    grid::{Direction, SquareGrid},
+    grid::{Connectivity, Direction, SquareGrid},
     solution::AocSolution,
 };
 
@@ -80,27 +77,8 @@ fn dig<'a>(instructions: &'a [DigInstruction]) -> SquareGrid<Option<&'a str>> {
 }
 
 fn count_outside<T>(grid: &SquareGrid<Option<T>>) -> usize {
-    if matches!(grid.get(0, 0), Some(Some(_))) {
-        unreachable!("0,0 should be empty")
-    }
-    let mut queue = vec![(0, 0)];
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
-    visited.insert((0, 0));
-    let mut count = 0;
-    while let Some((x, y)) = queue.pop() {
-        if matches!(grid.get(x, y), Some(Some(_))) {
-            continue;
-        }
-        count += 1;
-        let new_positions = grid
-            .neighbors_8(x, y)
-            .into_iter()
-            .filter(|p| !visited.contains(p))
-            .collect_vec();
-        queue.extend(new_positions.iter());
-        visited.extend(new_positions);
-    }
-    count
+    grid.reachable_from_border(Connectivity::Eight, Option::is_none)
+        .count()
 }
 
 fn count_inside<T>(grid: &SquareGrid<Option<T>>) -> usize {
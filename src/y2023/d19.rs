@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
 
 use nom::{
     branch::alt,
@@ -10,7 +12,11 @@ use nom::{
     IResult,
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    graph::dot::{Dot, Shape},
+    interval::{Interval, IntervalBox},
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -28,6 +34,22 @@ enum WorkflowCheck {
     Always,
 }
 
+impl Display for WorkflowCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkflowCheck::XAbove(v) => write!(f, "x>{}", v),
+            WorkflowCheck::XBelow(v) => write!(f, "x<{}", v),
+            WorkflowCheck::MAbove(v) => write!(f, "m>{}", v),
+            WorkflowCheck::MBelow(v) => write!(f, "m<{}", v),
+            WorkflowCheck::AAbove(v) => write!(f, "a>{}", v),
+            WorkflowCheck::ABelow(v) => write!(f, "a<{}", v),
+            WorkflowCheck::SAbove(v) => write!(f, "s>{}", v),
+            WorkflowCheck::SBelow(v) => write!(f, "s<{}", v),
+            WorkflowCheck::Always => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum WorkflowDestination<'a> {
     Workflow(&'a str),
@@ -35,6 +57,16 @@ enum WorkflowDestination<'a> {
     Accept,
 }
 
+impl<'a> WorkflowDestination<'a> {
+    fn name(&self) -> &'a str {
+        match self {
+            WorkflowDestination::Workflow(name) => name,
+            WorkflowDestination::Reject => "R",
+            WorkflowDestination::Accept => "A",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct WorkflowStep<'a> {
     check: WorkflowCheck,
@@ -187,255 +219,79 @@ impl AocSolution for Part1 {
     }
 }
 
-fn count_ranges(x: (u64, u64), m: (u64, u64), a: (u64, u64), s: (u64, u64)) -> u64 {
-    (x.1 - x.0) * (m.1 - m.0) * (a.1 - a.0) * (s.1 - s.0)
-}
+// one interval for each of x, m, a and s
+type PartRange = IntervalBox<u64, 4>;
 
-#[derive(Debug)]
-struct PartRange<'a> {
-    x: (u64, u64),
-    m: (u64, u64),
-    a: (u64, u64),
-    s: (u64, u64),
-    destination: WorkflowDestination<'a>,
+impl WorkflowCheck {
+    // the parts of `range` that pass and fail the check
+    fn split_range(&self, range: &PartRange) -> (Option<PartRange>, Option<PartRange>) {
+        let (axis, v, above) = match *self {
+            WorkflowCheck::XAbove(v) => (0, v, true),
+            WorkflowCheck::XBelow(v) => (0, v, false),
+            WorkflowCheck::MAbove(v) => (1, v, true),
+            WorkflowCheck::MBelow(v) => (1, v, false),
+            WorkflowCheck::AAbove(v) => (2, v, true),
+            WorkflowCheck::ABelow(v) => (2, v, false),
+            WorkflowCheck::SAbove(v) => (3, v, true),
+            WorkflowCheck::SBelow(v) => (3, v, false),
+            WorkflowCheck::Always => return (Some(*range), None),
+        };
+        if above {
+            let (fail, pass) = range.split_at(axis, v + 1);
+            (pass, fail)
+        } else {
+            range.split_at(axis, v)
+        }
+    }
 }
 
 fn run_range_workflows(workflows: &HashMap<&str, Vec<WorkflowStep>>) -> u64 {
-    let mut ranges = vec![PartRange {
-        x: (1, 4001),
-        m: (1, 4001),
-        a: (1, 4001),
-        s: (1, 4001),
-        destination: WorkflowDestination::Workflow("in"),
-    }];
+    let mut ranges = vec![(
+        PartRange::new([Interval::new(1, 4001); 4]),
+        WorkflowDestination::Workflow("in"),
+    )];
     let mut accepted = 0;
-    while let Some(PartRange {
-        x,
-        m,
-        a,
-        s,
-        destination,
-    }) = ranges.pop()
-    {
+    while let Some((range, destination)) = ranges.pop() {
         match destination {
             WorkflowDestination::Workflow(d) => {
-                let workflow = &workflows[d];
-                workflow
-                    .iter()
-                    .fold((x, m, a, s), |(x, m, a, s), check| match check.check {
-                        WorkflowCheck::XAbove(v) => {
-                            let (j, k) = x;
-                            if k < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if j < v {
-                                ranges.push(PartRange {
-                                    x: (v + 1, k),
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((j, v + 1), m, a, s)
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::XBelow(v) => {
-                            let (j, k) = x;
-                            if v < j {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if v < k {
-                                ranges.push(PartRange {
-                                    x: (j, v),
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((v, k), m, a, s)
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::MAbove(v) => {
-                            let (j, k) = m;
-                            if k < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if j < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m: (v + 1, k),
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                (x, (j, v + 1), a, s)
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::MBelow(v) => {
-                            let (j, k) = m;
-                            if v < j {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if v < k {
-                                ranges.push(PartRange {
-                                    x,
-                                    m: (j, v),
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                (x, (v, k), a, s)
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::AAbove(v) => {
-                            let (j, k) = a;
-                            if k < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if j < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a: (v + 1, k),
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                (x, m, (j, v + 1), s)
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::ABelow(v) => {
-                            let (j, k) = a;
-                            if v < j {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if v < k {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a: (j, v),
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                (x, m, (v, k), s)
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::SAbove(v) => {
-                            let (j, k) = s;
-                            if k < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if j < v {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s: (v + 1, k),
-                                    destination: check.destination.clone(),
-                                });
-                                (x, m, a, (j, v + 1))
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::SBelow(v) => {
-                            let (j, k) = s;
-                            if v < j {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s,
-                                    destination: check.destination.clone(),
-                                });
-                                ((0, 0), (0, 0), (0, 0), (0, 0))
-                            } else if v < k {
-                                ranges.push(PartRange {
-                                    x,
-                                    m,
-                                    a,
-                                    s: (j, v),
-                                    destination: check.destination.clone(),
-                                });
-                                (x, m, a, (v, k))
-                            } else {
-                                (x, m, a, s)
-                            }
-                        }
-                        WorkflowCheck::Always => {
-                            ranges.push(PartRange {
-                                x,
-                                m,
-                                a,
-                                s,
-                                destination: check.destination.clone(),
-                            });
-                            ((0, 0), (0, 0), (0, 0), (0, 0))
-                        }
-                    });
+                let mut remaining = Some(range);
+                for step in &workflows[d] {
+                    let Some(range) = remaining else {
+                        break;
+                    };
+                    let (pass, fail) = step.check.split_range(&range);
+                    ranges.extend(pass.map(|pass| (pass, step.destination.clone())));
+                    remaining = fail;
+                }
             }
             WorkflowDestination::Reject => {}
             WorkflowDestination::Accept => {
-                accepted += count_ranges(x, m, a, s);
+                accepted += range.volume();
             }
         }
     }
     accepted
 }
 
+// workflows in name order, with an edge for every step labelled by its check
+pub fn dot(input: &str) -> Dot {
+    let (workflows, _) = parse_input(input);
+    let mut dot = Dot::digraph("workflows");
+    dot.add_node("A", None, Some(Shape::DoubleCircle));
+    dot.add_node("R", None, Some(Shape::Circle));
+    for name in workflows.keys().sorted() {
+        dot.add_node(name, None, Some(Shape::Box));
+    }
+    for (name, steps) in workflows.iter().sorted_by_key(|(name, _)| **name) {
+        for step in steps {
+            let label = step.check.to_string();
+            let label = Some(label.as_str()).filter(|l| !l.is_empty());
+            dot.add_edge(name, step.destination.name(), label);
+        }
+    }
+    dot
+}
+
 impl AocSolution for Part2 {
     const PART: u32 = 2;
     fn solution_path() -> String {
@@ -448,6 +304,24 @@ impl AocSolution for Part2 {
     }
 }
 
+#[test]
+fn test_dot() {
+    let input = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}";
+    assert_eq!(dot(input).to_string(), include_str!("golden/d19.dot"));
+}
+
 #[test]
 fn p1_pull_examples() {
     Part1::get_examples();
@@ -1,6 +1,5 @@
 use std::collections::{HashMap, VecDeque};
 
-use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,7 +10,13 @@ use nom::{
     sequence::{preceded, separated_pair},
 };
 
-use crate::common::solution::AocSolution;
+use crate::common::{
+    graph::{
+        dot::{Dot, Shape},
+        Graph,
+    },
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -35,6 +40,16 @@ enum Module<'input> {
     Broadcaster,
 }
 
+impl<'input> Module<'input> {
+    fn name(&self) -> &'input str {
+        match self {
+            Module::FlipFlop { name, is_on: _ } => name,
+            Module::Conjunction { name, memory: _ } => name,
+            Module::Broadcaster => ModuleArray::BROADCASTER,
+        }
+    }
+}
+
 struct Pulse<'input> {
     source_name: &'input str,
     destination_name: &'input str,
@@ -43,7 +58,7 @@ struct Pulse<'input> {
 
 struct ModuleArray<'input> {
     modules: HashMap<&'input str, Module<'input>>,
-    connections: HashMap<&'input str, Vec<&'input str>>,
+    connections: Graph<'input>,
 }
 
 impl<'input> ModuleArray<'input> {
@@ -57,6 +72,15 @@ impl<'input> ModuleArray<'input> {
             destination_name: Self::BROADCASTER,
             pitch: PulsePitch::Low,
         }]);
+        let connections = &self.connections;
+        // by id, so no names are collected for every pulse
+        let successors = |name: &str| {
+            connections
+                .id(name)
+                .into_iter()
+                .flat_map(|id| connections.successors(id))
+                .map(|id| connections.name(*id))
+        };
         while let Some(pulse) = pulses.pop_back() {
             match pulse.pitch {
                 PulsePitch::Low => low_count += 1,
@@ -74,7 +98,7 @@ impl<'input> ModuleArray<'input> {
                                 *is_on = true;
                                 PulsePitch::High
                             };
-                            self.connections[name].iter().for_each(|m| {
+                            successors(name).for_each(|m| {
                                 pulses.push_front(Pulse {
                                     source_name: name,
                                     destination_name: m,
@@ -90,7 +114,7 @@ impl<'input> ModuleArray<'input> {
                         } else {
                             PulsePitch::High
                         };
-                        self.connections[name].iter().for_each(|m| {
+                        successors(name).for_each(|m| {
                             pulses.push_front(Pulse {
                                 source_name: name,
                                 destination_name: m,
@@ -99,15 +123,13 @@ impl<'input> ModuleArray<'input> {
                         })
                     }
                     Module::Broadcaster => {
-                        self.connections[ModuleArray::BROADCASTER]
-                            .iter()
-                            .for_each(|m| {
-                                pulses.push_front(Pulse {
-                                    source_name: ModuleArray::BROADCASTER,
-                                    destination_name: m,
-                                    pitch: pulse.pitch,
-                                })
-                            });
+                        successors(ModuleArray::BROADCASTER).for_each(|m| {
+                            pulses.push_front(Pulse {
+                                source_name: ModuleArray::BROADCASTER,
+                                destination_name: m,
+                                pitch: pulse.pitch,
+                            })
+                        });
                     }
                 });
         }
@@ -138,41 +160,37 @@ fn parse_input(input: &str) -> ModuleArray {
     .unwrap()
     .1;
     let mut module_array = ModuleArray {
-        modules: HashMap::from_iter(modules.iter().map(|(m, _)| {
-            (
-                match m {
-                    Module::FlipFlop { name, is_on: _ } => name,
-                    Module::Conjunction { name, memory: _ } => name,
-                    Module::Broadcaster => ModuleArray::BROADCASTER,
-                },
-                m.clone(),
-            )
-        })),
-        connections: HashMap::from_iter(modules.iter().map(|(m, t)| {
-            (
-                match m {
-                    Module::FlipFlop { name, is_on: _ } => name,
-                    Module::Conjunction { name, memory: _ } => name,
-                    Module::Broadcaster => ModuleArray::BROADCASTER,
-                },
-                t.iter().cloned().collect_vec(),
-            )
-        })),
+        modules: HashMap::from_iter(modules.iter().map(|(m, _)| (m.name(), m.clone()))),
+        connections: Graph::new(),
     };
+    for (m, t) in &modules {
+        module_array
+            .connections
+            .add_edges(m.name(), t.iter().copied());
+    }
     module_array.modules.iter_mut().for_each(|(name, m)| {
         if let Module::Conjunction { name: _, memory } = m {
-            module_array
-                .connections
-                .iter()
-                .filter(|(_, t)| t.contains(name))
-                .for_each(|(n, _)| {
-                    memory.insert(n, PulsePitch::Low);
-                });
+            for input in module_array.connections.predecessor_names(name) {
+                memory.insert(input, PulsePitch::Low);
+            }
         }
     });
     module_array
 }
 
+// flip-flops are boxes, conjunctions diamonds and modules that only receive pulses ellipses
+pub fn dot(input: &str) -> Dot {
+    let module_array = parse_input(input);
+    module_array
+        .connections
+        .to_dot("modules", |name| match module_array.modules.get(name) {
+            Some(Module::FlipFlop { .. }) => Shape::Box,
+            Some(Module::Conjunction { .. }) => Shape::Diamond,
+            Some(Module::Broadcaster) => Shape::DoubleCircle,
+            None => Shape::Ellipse,
+        })
+}
+
 impl AocSolution for Part1 {
     const PART: u32 = 1;
     fn solution_path() -> String {
@@ -200,6 +218,16 @@ impl AocSolution for Part2 {
     }
 }
 
+#[test]
+fn test_dot() {
+    let input = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+    assert_eq!(dot(input).to_string(), include_str!("golden/d20.dot"));
+}
+
 #[test]
 fn p1_pull_examples() {
     Part1::get_examples();
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use num::Integer;
 
-use crate::common::{grid::SquareGrid, solution::AocSolution};
+use num::{BigInt, Integer};
+
+use crate::common::{
+    grid::{BitGrid, Point2, SquareGrid},
+    poly::Polynomial,
+    solution::AocSolution,
+};
 
 struct Part1 {}
 struct Part2 {}
@@ -18,10 +23,6 @@ struct Garden {
     start: (usize, usize),
 }
 
-fn neighbors(x: i64, y: i64) -> Vec<(i64, i64)> {
-    vec![(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
-}
-
 impl Garden {
     fn count_reachable_in_exact_steps(&self, steps: usize) -> usize {
         let mut visited: HashMap<(usize, usize), usize> = HashMap::new();
@@ -55,72 +56,70 @@ impl Garden {
         }
     }
 
+    // plots can be stepped on and off again, so a plot is reachable in exactly `steps` steps
+    // when its distance is at most `steps` and has the same parity
     fn count_reachable_in_exact_steps_infinite(&self, steps: usize) -> usize {
-        let mut visited: HashMap<(i64, i64), usize> = HashMap::new();
+        let garden = self.grid.tiled();
         let (start_x, start_y) = self.start;
-        let start = (start_x as i64, start_y as i64);
-        visited.insert(start, 0);
-        self.count_reachable_helper_infinite(start, 0, &mut visited, steps);
-        visited.into_values().filter(|v| v.is_even()).count()
-    }
-
-    fn count_reachable_helper_infinite(
-        &self,
-        (x, y): (i64, i64),
-        depth: usize,
-        visited: &mut HashMap<(i64, i64), usize>,
-        max_depth: usize,
-    ) {
-        if depth == max_depth {
-            return;
-        }
-        let new_depth = depth + 1;
-        for neighbor in neighbors(x, y) {
-            if let Some(GardenBlock::Rock) =
-                self.grid.get_infinitely_looping(neighbor.0, neighbor.1)
-            {
-                continue;
+        let start = Point2::new(start_x as i64, start_y as i64);
+        // everything within `steps` of the start, as bits since hashing millions of plots is slow
+        let reach = steps as i64;
+        let side = 2 * reach as usize + 1;
+        let mut seen = BitGrid::new(side, side);
+        let mut mark_seen = |p: Point2<i64>| {
+            let offset = p - start + Point2::new(reach, reach);
+            let (x, y) = (offset.x as usize, offset.y as usize);
+            let newly_seen = !seen.get(x, y);
+            seen.set(x, y, true);
+            newly_seen
+        };
+        mark_seen(start);
+        let mut layer = vec![start];
+        let mut count = 0;
+        for distance in 0..=steps {
+            if distance % 2 == steps % 2 {
+                count += layer.len();
             }
-            if let Some(prev_depth) = visited.get(&neighbor) {
-                if *prev_depth <= new_depth {
-                    continue;
-                }
+            if distance == steps {
+                break;
             }
-            visited.insert(neighbor, new_depth);
-            self.count_reachable_helper_infinite(neighbor, new_depth, visited, max_depth);
+            layer = layer
+                .iter()
+                .flat_map(|p| garden.neighbors_4(*p))
+                .filter(|(_, block)| !matches!(block, GardenBlock::Rock))
+                .map(|(n, _)| n)
+                .filter(|n| mark_seen(*n))
+                .collect_vec();
         }
+        count
+    }
+
+    // the garden repeats every `width` steps out from the middle, so counts taken a whole number
+    // of gardens apart follow a quadratic, though only once the first few gardens are behind
+    fn count_reachable_by_fitting(&self, steps: usize, first_sample: usize) -> BigInt {
+        let width = self.grid.width;
+        let points = (first_sample..first_sample + 3)
+            .map(|k| {
+                let count = self.count_reachable_in_exact_steps_infinite(steps % width + k * width);
+                (k as i64, count as i64)
+            })
+            .collect_vec();
+        Polynomial::interpolate(&points)
+            .eval_integer((steps / width) as i64)
+            .expect("a count of plots is a whole number")
     }
 }
 
 fn parse_input(input: &str) -> Garden {
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
-    let grid = SquareGrid {
-        width,
-        height,
-        data: input
-            .lines()
-            .flat_map(|l| l.chars())
-            .map(|c| match c {
-                '.' | 'S' => GardenBlock::Plot,
-                '#' => GardenBlock::Rock,
-                _ => unreachable!(),
-            })
-            .collect_vec(),
-    };
-    let (start_y, start_line) = input
-        .lines()
-        .enumerate()
-        .find(|(_, l)| l.contains('S'))
-        .unwrap();
-    let (start_x, _) = start_line
-        .chars()
-        .enumerate()
-        .find(|(_, c)| *c == 'S')
-        .unwrap();
+    let (grid, markers) = SquareGrid::parse_with_markers(input, &['S'], |c| match c {
+        '.' | 'S' => Ok(GardenBlock::Plot),
+        '#' => Ok(GardenBlock::Rock),
+        _ => Err("not a garden plot or rock"),
+    })
+    .unwrap();
     Garden {
         grid,
-        start: (start_x, start_y),
+        start: markers[&'S'][0],
     }
 }
 
@@ -146,7 +145,8 @@ impl AocSolution for Part2 {
     }
 
     fn implementation(input: &str) -> String {
-        todo!("{}", input)
+        let garden = parse_input(input);
+        garden.count_reachable_by_fitting(26501365, 0).to_string()
     }
 }
 
@@ -203,6 +203,12 @@ fn test_p2() {
         parsed.count_reachable_in_exact_steps_infinite(5000),
         16733044
     );
+    for (steps, expected) in [(500, 167004), (1000, 668697), (5000, 16733044)] {
+        assert_eq!(
+            parsed.count_reachable_by_fitting(steps, 3),
+            BigInt::from(expected)
+        );
+    }
 }
 
 #[test]
@@ -1,3 +1,5 @@
+use crate::common::graph::dot::Dot;
+
 mod d01;
 mod d02;
 mod d03;
@@ -19,3 +21,12 @@ mod d18;
 mod d19;
 mod d20;
 mod d21;
+
+// the days whose input can be drawn as a graph
+pub fn dot_exporter(day: u32) -> Option<fn(&str) -> Dot> {
+    match day {
+        19 => Some(d19::dot),
+        20 => Some(d20::dot),
+        _ => None,
+    }
+}